@@ -1,19 +1,27 @@
 use crate::{
-    data::{self, Allowances, Metadata, OwnedTokens, Owners},
+    data::{
+        self, AllTokens, Allowances, EventsMode, Metadata, OwnedTokens, Owners,
+        TransferAllowlist, TransferPolicy,
+    },
     event::CEP47Event,
     Meta, TokenId,
 };
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 use casper_types::{ApiError, ContractHash, ContractPackageHash, Key, U256};
 use casperlabs_contract_utils::{ContractContext, ContractStorage};
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
 #[repr(u16)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     PermissionDenied = 1,
     WrongArguments = 2,
     TokenIdAlreadyExists = 3,
     TokenIdDoesntExist = 4,
+    TransferNotAllowed = 5,
+    ContractPaused = 6,
+    TokenIdsExhausted = 7,
 }
 
 impl From<Error> for ApiError {
@@ -22,6 +30,23 @@ impl From<Error> for ApiError {
     }
 }
 
+impl TryFrom<u16> for Error {
+    type Error = ();
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(Error::PermissionDenied),
+            2 => Ok(Error::WrongArguments),
+            3 => Ok(Error::TokenIdAlreadyExists),
+            4 => Ok(Error::TokenIdDoesntExist),
+            5 => Ok(Error::TransferNotAllowed),
+            6 => Ok(Error::ContractPaused),
+            7 => Ok(Error::TokenIdsExhausted),
+            _ => Err(()),
+        }
+    }
+}
+
 pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
     fn init(
         &mut self,
@@ -35,12 +60,16 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         data::set_symbol(symbol);
         data::set_meta(meta);
         data::set_total_supply(U256::zero());
+        data::set_next_token_id(U256::zero());
         data::set_contract_hash(contract_hash);
         data::set_package_hash(package_hash);
         Owners::init();
         OwnedTokens::init();
         Metadata::init();
         Allowances::init();
+        AllTokens::init();
+        TransferAllowlist::init();
+        data::set_transfer_policy(TransferPolicy::Free);
     }
 
     fn name(&self) -> String {
@@ -87,6 +116,46 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         OwnedTokens::instance().get_token_by_index(&owner, &index)
     }
 
+    fn token_by_index(&self, index: U256) -> Option<TokenId> {
+        AllTokens::instance().get_token_by_index(&index)
+    }
+
+    fn all_tokens(&self, start: U256, limit: U256) -> Vec<TokenId> {
+        let all_tokens_dict = AllTokens::instance();
+        let end = data::total_supply().min(start.saturating_add(limit));
+        let mut tokens = Vec::new();
+        let mut index = start;
+        while index < end {
+            tokens.push(all_tokens_dict.get_token_by_index(&index).unwrap_or_revert());
+            index += U256::one();
+        }
+        tokens
+    }
+
+    fn tokens_of_owner(&self, owner: Key, start: U256, limit: U256) -> Vec<TokenId> {
+        let owned_tokens_dict = OwnedTokens::instance();
+        let end = owned_tokens_dict
+            .get_balances(&owner)
+            .min(start.saturating_add(limit));
+        let mut tokens = Vec::new();
+        let mut index = start;
+        while index < end {
+            tokens.push(
+                owned_tokens_dict
+                    .get_token_by_index(&owner, &index)
+                    .unwrap_or_revert(),
+            );
+            index += U256::one();
+        }
+        tokens
+    }
+
+    fn get_tokens(&mut self, owner: Key, offset: U256, limit: U256) -> Vec<TokenId> {
+        let tokens = self.tokens_of_owner(owner, offset, limit);
+        data::set_result(tokens.clone());
+        tokens
+    }
+
     fn validate_token_ids(&self, token_ids: Vec<TokenId>) -> bool {
         for token_id in &token_ids {
             if self.owner_of(*token_id).is_some() {
@@ -96,18 +165,52 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         true
     }
 
+    // `mint` keeps the counter past every explicitly minted id, so taken ids are only
+    // skipped here for tokens minted before the counter tracked them.
+    fn generate_token_ids(&mut self, count: u32) -> Result<Vec<TokenId>, Error> {
+        let mut token_ids = Vec::new();
+        let mut next_token_id = data::next_token_id();
+        while token_ids.len() < count.try_into().unwrap() {
+            if self.owner_of(next_token_id).is_none() {
+                token_ids.push(next_token_id);
+            }
+            next_token_id = next_token_id
+                .checked_add(U256::one())
+                .ok_or(Error::TokenIdsExhausted)?;
+        }
+        data::set_next_token_id(next_token_id);
+        Ok(token_ids)
+    }
+
     fn mint(
         &mut self,
         recipient: Key,
-        token_ids: Vec<TokenId>,
+        token_ids: Option<Vec<TokenId>>,
         token_metas: Vec<Meta>,
     ) -> Result<Vec<TokenId>, Error> {
+        let token_ids = match token_ids {
+            Some(token_ids) => {
+                if let Some(max_token_id) = token_ids.iter().max() {
+                    if *max_token_id >= data::next_token_id() {
+                        data::set_next_token_id(
+                            max_token_id
+                                .checked_add(U256::one())
+                                .ok_or(Error::TokenIdsExhausted)?,
+                        );
+                    }
+                }
+                token_ids
+            }
+            None => self.generate_token_ids(token_metas.len().try_into().unwrap())?,
+        };
+
         if token_ids.len() != token_metas.len() {
             return Err(Error::WrongArguments);
         };
 
+        let mut unique_token_ids = BTreeSet::new();
         for token_id in &token_ids {
-            if self.owner_of(*token_id).is_some() {
+            if !unique_token_ids.insert(*token_id) || self.owner_of(*token_id).is_some() {
                 return Err(Error::TokenIdAlreadyExists);
             }
         }
@@ -115,11 +218,15 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         let owners_dict = Owners::instance();
         let owned_tokens_dict = OwnedTokens::instance();
         let metadata_dict = Metadata::instance();
+        let all_tokens_dict = AllTokens::instance();
 
+        let mut all_tokens_length = data::total_supply();
         for (token_id, token_meta) in token_ids.iter().zip(&token_metas) {
             metadata_dict.set(token_id, token_meta.clone());
             owners_dict.set(token_id, recipient);
             owned_tokens_dict.set_token(&recipient, token_id);
+            all_tokens_dict.set_token(&all_tokens_length, token_id);
+            all_tokens_length += U256::one();
         }
 
         let minted_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
@@ -138,12 +245,11 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
     fn mint_copies(
         &mut self,
         recipient: Key,
-        token_ids: Vec<TokenId>,
         token_meta: Meta,
         count: u32,
     ) -> Result<Vec<TokenId>, Error> {
         let token_metas = vec![token_meta; count.try_into().unwrap()];
-        self.mint(recipient, token_ids, token_metas)
+        self.mint(recipient, None, token_metas)
     }
 
     fn burn(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
@@ -163,6 +269,7 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         let owned_tokens_dict = OwnedTokens::instance();
         let metadata_dict = Metadata::instance();
         let allowances_dict = Allowances::instance();
+        let all_tokens_dict = AllTokens::instance();
 
         for token_id in &token_ids {
             match owners_dict.get(token_id) {
@@ -177,11 +284,14 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
             }
         }
 
+        let mut all_tokens_length = data::total_supply();
         for token_id in &token_ids {
             owned_tokens_dict.remove_token(&owner, token_id);
             metadata_dict.remove(token_id);
             owners_dict.remove(token_id);
             allowances_dict.remove(&owner, token_id);
+            all_tokens_dict.remove_token(&all_tokens_length, token_id);
+            all_tokens_length -= U256::one();
         }
 
         let burnt_tokens_count: U256 = From::<u64>::from(token_ids.len().try_into().unwrap());
@@ -245,6 +355,8 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         recipient: Key,
         token_ids: Vec<TokenId>,
     ) -> Result<(), Error> {
+        self.check_transfer_policy(recipient)?;
+
         let owners_dict = Owners::instance();
         let owned_tokens_dict = OwnedTokens::instance();
 
@@ -275,6 +387,36 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
+    fn transfer_policy(&self) -> TransferPolicy {
+        data::transfer_policy()
+    }
+
+    fn set_transfer_policy(&mut self, transfer_policy: TransferPolicy) {
+        data::set_transfer_policy(transfer_policy);
+    }
+
+    fn is_transfer_allowed(&self, recipient: Key) -> bool {
+        TransferAllowlist::instance().is_allowed(&recipient)
+    }
+
+    fn set_transfer_allowed(&mut self, recipient: Key, allowed: bool) {
+        TransferAllowlist::instance().set(&recipient, allowed);
+    }
+
+    fn check_transfer_policy(&self, recipient: Key) -> Result<(), Error> {
+        match data::transfer_policy() {
+            TransferPolicy::Free => Ok(()),
+            TransferPolicy::Soulbound => Err(Error::TransferNotAllowed),
+            TransferPolicy::Allowlist => {
+                if self.is_transfer_allowed(recipient) {
+                    Ok(())
+                } else {
+                    Err(Error::TransferNotAllowed)
+                }
+            }
+        }
+    }
+
     fn is_approved(&self, owner: Key, token_id: TokenId, spender: Key) -> bool {
         let allowances_dict = Allowances::instance();
         if let Some(spender_of) = allowances_dict.get(&owner, &token_id) {
@@ -285,6 +427,14 @@ pub trait CEP47<Storage: ContractStorage>: ContractContext<Storage> {
         false
     }
 
+    fn events_mode(&self) -> EventsMode {
+        data::events_mode()
+    }
+
+    fn set_events_mode(&mut self, events_mode: EventsMode) {
+        data::set_events_mode(events_mode);
+    }
+
     fn emit(&mut self, event: CEP47Event) {
         data::emit(&event);
     }
@@ -0,0 +1,64 @@
+use crate::{
+    cep47::{Error, CEP47},
+    Meta, TokenId,
+};
+use alloc::vec::Vec;
+use casper_types::Key;
+use casperlabs_contract_utils::ContractStorage;
+use casperlabs_pausable::PAUSABLE;
+
+/// Pause scopes checked in addition to the contract-wide pause.
+pub const TRANSFER_SCOPE: &str = "transfer";
+pub const MINT_SCOPE: &str = "mint";
+pub const BURN_SCOPE: &str = "burn";
+pub const APPROVE_SCOPE: &str = "approve";
+
+/// Pause-checked variants of the `CEP47` mutators. They do not override the `CEP47`
+/// methods, so a pausable contract must wire its entry points to the `pausable_*` ones.
+pub trait CEP47PAUSABLE<Storage: ContractStorage>: CEP47<Storage> + PAUSABLE<Storage> {
+    fn pausable_mint(
+        &mut self,
+        recipient: Key,
+        token_ids: Option<Vec<TokenId>>,
+        token_metas: Vec<Meta>,
+    ) -> Result<Vec<TokenId>, Error> {
+        self._when_not_paused(MINT_SCOPE)?;
+        CEP47::mint(self, recipient, token_ids, token_metas)
+    }
+    fn pausable_mint_copies(
+        &mut self,
+        recipient: Key,
+        token_meta: Meta,
+        count: u32,
+    ) -> Result<Vec<TokenId>, Error> {
+        self._when_not_paused(MINT_SCOPE)?;
+        CEP47::mint_copies(self, recipient, token_meta, count)
+    }
+    fn pausable_burn(&mut self, owner: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        self._when_not_paused(BURN_SCOPE)?;
+        CEP47::burn(self, owner, token_ids)
+    }
+    fn pausable_approve(&mut self, spender: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        self._when_not_paused(APPROVE_SCOPE)?;
+        CEP47::approve(self, spender, token_ids)
+    }
+    fn pausable_transfer(&mut self, recipient: Key, token_ids: Vec<TokenId>) -> Result<(), Error> {
+        self._when_not_paused(TRANSFER_SCOPE)?;
+        CEP47::transfer(self, recipient, token_ids)
+    }
+    fn pausable_transfer_from(
+        &mut self,
+        owner: Key,
+        recipient: Key,
+        token_ids: Vec<TokenId>,
+    ) -> Result<(), Error> {
+        self._when_not_paused(TRANSFER_SCOPE)?;
+        CEP47::transfer_from(self, owner, recipient, token_ids)
+    }
+    fn _when_not_paused(&self, scope: &str) -> Result<(), Error> {
+        if self.is_scope_paused(scope) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+}
@@ -4,10 +4,13 @@ use alloc::{
     vec::Vec,
 };
 use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{ContractHash, ContractPackageHash, Key, URef, U256};
+use casper_types::{
+    bytesrepr::ToBytes, CLTyped, ContractHash, ContractPackageHash, Key, URef, U256,
+};
 use casperlabs_contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
 
-use crate::{event::CEP47Event, Meta, TokenId};
+use crate::{event::CEP47Event, Error, Meta, TokenId};
+use core::convert::TryFrom;
 
 const BALANCES_DICT: &str = "balances";
 pub const ALLOWANCES_DICT: &str = "allowances";
@@ -15,15 +18,62 @@ const METADATA_DICT: &str = "metadata";
 const OWNERS_DICT: &str = "owners";
 const OWNED_TOKENS_BY_INDEX_DICT: &str = "owned_tokens_by_index";
 const OWNED_INDEXES_BY_TOKEN_DICT: &str = "owned_indexes_by_token";
+const ALL_TOKENS_BY_INDEX_DICT: &str = "all_tokens_by_index";
+const ALL_INDEXES_BY_TOKEN_DICT: &str = "all_indexes_by_token";
+const TRANSFER_ALLOWLIST_DICT: &str = "transfer_allowlist";
 const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
 
 pub const NAME: &str = "name";
 pub const META: &str = "meta";
 pub const SYMBOL: &str = "symbol";
 pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const NEXT_TOKEN_ID: &str = "next_token_id";
+pub const TRANSFER_POLICY: &str = "transfer_policy";
+pub const EVENTS_MODE: &str = "events_mode";
+pub const RESULT: &str = "result";
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_CONTRACT_PACKAGE_HASH: &str = "self_contract_package_hash";
 
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TransferPolicy {
+    Free = 0,
+    Soulbound = 1,
+    Allowlist = 2,
+}
+
+impl TryFrom<u8> for TransferPolicy {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TransferPolicy::Free),
+            1 => Ok(TransferPolicy::Soulbound),
+            2 => Ok(TransferPolicy::Allowlist),
+            _ => Err(Error::WrongArguments),
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EventsMode {
+    PerToken = 0,
+    Batched = 1,
+}
+
+impl TryFrom<u8> for EventsMode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(EventsMode::PerToken),
+            1 => Ok(EventsMode::Batched),
+            _ => Err(Error::WrongArguments),
+        }
+    }
+}
+
 pub struct Owners {
     dict: Dict,
 }
@@ -158,6 +208,74 @@ impl OwnedTokens {
     }
 }
 
+pub struct AllTokens {
+    tokens_dict: Dict,
+    indexes_dict: Dict,
+}
+
+impl AllTokens {
+    pub fn instance() -> AllTokens {
+        AllTokens {
+            tokens_dict: Dict::instance(ALL_TOKENS_BY_INDEX_DICT),
+            indexes_dict: Dict::instance(ALL_INDEXES_BY_TOKEN_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ALL_TOKENS_BY_INDEX_DICT);
+        Dict::init(ALL_INDEXES_BY_TOKEN_DICT);
+    }
+
+    pub fn get_token_by_index(&self, index: &U256) -> Option<TokenId> {
+        self.tokens_dict.get(&index.to_string())
+    }
+
+    pub fn get_index_by_token(&self, value: &TokenId) -> Option<U256> {
+        self.indexes_dict.get(&value.to_string())
+    }
+
+    pub fn set_token(&self, length: &U256, value: &TokenId) {
+        self.indexes_dict.set(&value.to_string(), *length);
+        self.tokens_dict.set(&length.to_string(), *value);
+    }
+
+    pub fn remove_token(&self, length: &U256, value: &TokenId) {
+        let index = self.get_index_by_token(value).unwrap_or_revert();
+        let last_index = *length - 1;
+        if index < last_index {
+            let last = self.get_token_by_index(&last_index).unwrap_or_revert();
+            self.indexes_dict.set(&last.to_string(), index);
+            self.tokens_dict.set(&index.to_string(), last);
+        }
+        self.tokens_dict.remove::<TokenId>(&last_index.to_string());
+        self.indexes_dict.remove::<U256>(&value.to_string());
+    }
+}
+
+pub struct TransferAllowlist {
+    dict: Dict,
+}
+
+impl TransferAllowlist {
+    pub fn instance() -> TransferAllowlist {
+        TransferAllowlist {
+            dict: Dict::instance(TRANSFER_ALLOWLIST_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TRANSFER_ALLOWLIST_DICT)
+    }
+
+    pub fn is_allowed(&self, recipient: &Key) -> bool {
+        self.dict.get_by_key(recipient).unwrap_or_default()
+    }
+
+    pub fn set(&self, recipient: &Key, allowed: bool) {
+        self.dict.set_by_key(recipient, allowed);
+    }
+}
+
 pub struct Allowances {
     dict: Dict,
 }
@@ -195,6 +313,10 @@ impl Allowances {
     }
 }
 
+pub fn set_result<T: ToBytes + CLTyped>(value: T) {
+    set_key(RESULT, value);
+}
+
 pub fn name() -> String {
     get_key(NAME).unwrap_or_revert()
 }
@@ -227,6 +349,32 @@ pub fn set_total_supply(total_supply: U256) {
     set_key(TOTAL_SUPPLY, total_supply);
 }
 
+pub fn next_token_id() -> TokenId {
+    get_key(NEXT_TOKEN_ID).unwrap_or_default()
+}
+
+pub fn set_next_token_id(next_token_id: TokenId) {
+    set_key(NEXT_TOKEN_ID, next_token_id);
+}
+
+pub fn transfer_policy() -> TransferPolicy {
+    let policy: u8 = get_key(TRANSFER_POLICY).unwrap_or_default();
+    TransferPolicy::try_from(policy).unwrap_or_revert()
+}
+
+pub fn set_transfer_policy(transfer_policy: TransferPolicy) {
+    set_key(TRANSFER_POLICY, transfer_policy as u8);
+}
+
+pub fn events_mode() -> EventsMode {
+    let mode: u8 = get_key(EVENTS_MODE).unwrap_or_default();
+    EventsMode::try_from(mode).unwrap_or_revert()
+}
+
+pub fn set_events_mode(events_mode: EventsMode) {
+    set_key(EVENTS_MODE, events_mode as u8);
+}
+
 pub fn set_contract_hash(contract_hash: ContractHash) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
 }
@@ -244,6 +392,13 @@ pub fn get_package_hash() -> ContractPackageHash {
 }
 
 pub fn emit(event: &CEP47Event) {
+    match events_mode() {
+        EventsMode::PerToken => emit_per_token(event),
+        EventsMode::Batched => emit_batched(event),
+    }
+}
+
+fn emit_per_token(event: &CEP47Event) {
     match event {
         CEP47Event::Mint {
             recipient,
@@ -307,3 +462,63 @@ pub fn emit(event: &CEP47Event) {
         }
     };
 }
+
+fn emit_batched(event: &CEP47Event) {
+    let mut param = BTreeMap::new();
+    param.insert(CONTRACT_PACKAGE_HASH, get_package_hash().to_string());
+    match event {
+        CEP47Event::Mint {
+            recipient,
+            token_ids,
+        } => {
+            param.insert("event_type", "cep47_mint_batch".to_string());
+            param.insert("recipient", recipient.to_string());
+            insert_token_ids(&mut param, token_ids);
+        }
+        CEP47Event::Burn { owner, token_ids } => {
+            param.insert("event_type", "cep47_burn_batch".to_string());
+            param.insert("owner", owner.to_string());
+            insert_token_ids(&mut param, token_ids);
+        }
+        CEP47Event::Approve {
+            owner,
+            spender,
+            token_ids,
+        } => {
+            param.insert("event_type", "cep47_approve_batch".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("spender", spender.to_string());
+            insert_token_ids(&mut param, token_ids);
+        }
+        CEP47Event::Transfer {
+            sender,
+            recipient,
+            token_ids,
+        } => {
+            param.insert("event_type", "cep47_transfer_batch".to_string());
+            param.insert("sender", sender.to_string());
+            param.insert("recipient", recipient.to_string());
+            insert_token_ids(&mut param, token_ids);
+        }
+        CEP47Event::MetadataUpdate { token_id } => {
+            param.insert("event_type", "cep47_metadata_update".to_string());
+            param.insert("token_id", token_id.to_string());
+        }
+    };
+    storage::new_uref(param);
+}
+
+// Contiguous ascending ids are written as an inclusive range, anything else as a list.
+fn insert_token_ids(param: &mut BTreeMap<&str, String>, token_ids: &[TokenId]) {
+    let is_range = token_ids.len() > 1
+        && token_ids
+            .windows(2)
+            .all(|pair| pair[0].checked_add(U256::one()) == Some(pair[1]));
+    if is_range {
+        param.insert("token_id_start", token_ids[0].to_string());
+        param.insert("token_id_end", token_ids[token_ids.len() - 1].to_string());
+    } else {
+        let token_ids: Vec<String> = token_ids.iter().map(|id| id.to_string()).collect();
+        param.insert("token_ids", token_ids.join(","));
+    }
+}
@@ -3,10 +3,15 @@
 extern crate alloc;
 
 mod cep47;
+#[cfg(feature = "pausable")]
+mod cep47_pausable;
 pub mod data;
 pub mod event;
 
 pub use cep47::{Error, CEP47};
+#[cfg(feature = "pausable")]
+pub use cep47_pausable::{APPROVE_SCOPE, BURN_SCOPE, CEP47PAUSABLE, MINT_SCOPE, TRANSFER_SCOPE};
+pub use data::{EventsMode, TransferPolicy};
 pub use casperlabs_contract_utils;
 
 use alloc::{collections::BTreeMap, string::String};
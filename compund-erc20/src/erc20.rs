@@ -1,12 +1,12 @@
 use crate::data;
-use crate::data::{account_zero_address, zero_address, Error as Err};
+use crate::data::Error as Err;
 use crate::event::*;
 use alloc::string::String;
 use casper_contract::contract_api::runtime;
 use casper_types::ContractHash;
 use casper_types::Key;
 use casper_types::{ContractPackageHash, U256};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
 use compound_casper_erc20::{Address, Error, ERC20 as CasperErc20};
 use std::collections::BTreeMap;
 
@@ -69,9 +69,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn transfer(&self, recipient: Address, amount: U256) -> Result<(), Error> {
-        if recipient == Address::from(zero_address())
-            || recipient == Address::from(account_zero_address())
-        {
+        if is_null_key(&Key::from(recipient)) {
             runtime::revert(Err::CompoundErc20ZeroAddress1);
         }
         let ret = CasperErc20::default().transfer(recipient, amount);
@@ -98,9 +96,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn transfer_from(&self, owner: Address, recipient: Address, amount: U256) -> Result<(), Error> {
-        if recipient == Address::from(zero_address())
-            || recipient == Address::from(account_zero_address())
-        {
+        if is_null_key(&Key::from(recipient)) {
             runtime::revert(Err::CompoundErc20ZeroAddress2);
         }
         let ret = CasperErc20::default().transfer_from(owner, recipient, amount);
@@ -7,6 +7,7 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     ApiError, CLTyped, Key, RuntimeArgs, URef, U128, U256,
 };
@@ -93,6 +94,20 @@ impl Dict {
     }
 }
 
+/// The canonical "zero address": `account-hash-000…`.
+pub fn null_key() -> Key {
+    Key::Account(AccountHash::new([0u8; 32]))
+}
+
+/// Zero-address check. Also accepts the legacy `hash-000…` form.
+pub fn is_null_key(key: &Key) -> bool {
+    match key {
+        Key::Account(account_hash) => account_hash.value() == [0u8; 32],
+        Key::Hash(hash) => *hash == [0u8; 32],
+        _ => false,
+    }
+}
+
 pub fn key_to_str(key: &Key) -> String {
     match key {
         Key::Account(account) => account.to_string(),
@@ -1,13 +1,10 @@
-use crate::{
-    account_zero_address, data::Error as Errors, event::*, set_contract_hash, set_package_hash,
-    zero_address,
-};
+use crate::{data::Error as Errors, event::*, set_contract_hash, set_package_hash};
 use alloc::string::String;
 use casper_contract::contract_api::runtime;
 use casper_types::ContractHash;
 use casper_types::Key;
 use casper_types::{ContractPackageHash, U256};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
 use curve_casper_erc20::{Address, Error, ERC20 as CasperErc20};
 use std::collections::BTreeMap;
 
@@ -70,9 +67,7 @@ pub trait CURVEERC20<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn transfer(&self, recipient: Address, amount: U256) -> Result<(), Error> {
-        if recipient == Address::from(zero_address())
-            || recipient == Address::from(account_zero_address())
-        {
+        if is_null_key(&Key::from(recipient)) {
             runtime::revert(Errors::Erc20CurveZeroAddress1);
         }
         let ret = CasperErc20::default().transfer(recipient, amount);
@@ -99,9 +94,7 @@ pub trait CURVEERC20<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn transfer_from(&self, owner: Address, recipient: Address, amount: U256) -> Result<(), Error> {
-        if recipient == Address::from(zero_address())
-            || recipient == Address::from(account_zero_address())
-        {
+        if is_null_key(&Key::from(recipient)) {
             runtime::revert(Errors::Erc20CurveZeroAddress2);
         }
         let ret = CasperErc20::default().transfer_from(owner, recipient, amount);
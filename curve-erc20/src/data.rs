@@ -1,5 +1,5 @@
 use casper_types::{ApiError, ContractHash, ContractPackageHash, Key};
-use casperlabs_contract_utils::{get_key, set_key};
+use casperlabs_contract_utils::{get_key, null_key, set_key};
 
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
@@ -20,16 +20,8 @@ pub fn get_package_hash() -> ContractPackageHash {
     get_key(CONTRACT_PACKAGE_HASH).unwrap_or_default()
 }
 
-pub fn zero_address() -> Key {
-    Key::from_formatted_str("hash-0000000000000000000000000000000000000000000000000000000000000000")
-        .unwrap()
-}
-
 pub fn account_zero_address() -> Key {
-    Key::from_formatted_str(
-        "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-    )
-    .unwrap()
+    null_key()
 }
 
 #[repr(u16)]
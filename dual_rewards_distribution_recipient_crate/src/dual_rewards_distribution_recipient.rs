@@ -1,8 +1,9 @@
 //use crate::data::{self, Allowances, Balances, Nonces};
 use crate::data;
 use casper_contract::contract_api::runtime;
-use casper_types::ApiError;
+use casper_types::{ApiError, ContractPackageHash, Key};
 use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_owned::OWNED;
 
 #[repr(u16)]
 pub enum Error {
@@ -16,12 +17,28 @@ impl From<Error> for ApiError {
     }
 }
 pub trait DUALREWARDSDISTRIBUTIONRECIPIENT<Storage: ContractStorage>:
-    ContractContext<Storage>
+    ContractContext<Storage> + OWNED<Storage>
 {
-    fn init(&self) {}
+    fn init(
+        &mut self,
+        owner: Key,
+        dual_rewards_distribution: Key,
+        contract_hash: Key,
+        package_hash: ContractPackageHash,
+    ) {
+        OWNED::init(self, owner, contract_hash, package_hash);
+        data::set_dual_rewards_distribution(dual_rewards_distribution);
+    }
     fn only_dual_rewards_distribution(&self) {
         if !(self.get_caller() == data::get_dual_rewards_distribution()) {
             runtime::revert(ApiError::from(Error::NotDualRewardsDistribution));
         }
     }
+    fn dual_rewards_distribution(&self) -> Key {
+        data::get_dual_rewards_distribution()
+    }
+    fn set_dual_rewards_distribution(&self, dual_rewards_distribution: Key) {
+        OWNED::only_owner(self);
+        data::set_dual_rewards_distribution(dual_rewards_distribution);
+    }
 }
@@ -7,7 +7,7 @@ use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use casper_types::{
     system::mint::Error as MintError, ApiError, ContractPackageHash, Key, URef, U256,
 };
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{null_key, ContractContext, ContractStorage};
 
 pub enum ERC20Event {
     Approval {
@@ -64,6 +64,8 @@ pub enum Error {
     UniswapV2CoreERC20UnderFlow4 = 9,
     /// 65,546 for (UniswapV2 Core ERC20 UnderFlow5)
     UniswapV2CoreERC20UnderFlow5 = 10,
+    /// 65,547 for (ERC20 Contract Paused)
+    ContractPaused = 11,
 }
 
 impl From<Error> for ApiError {
@@ -202,10 +204,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
                 .ok_or(Error::UniswapV2CoreERC20OverFlow3)
                 .unwrap_or_revert(),
         );
-        let address_0: Key = Key::from_formatted_str(
-            "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-        )
-        .unwrap();
+        let address_0: Key = null_key();
         self.emit(&ERC20Event::Transfer {
             from: address_0,
             to: recipient,
@@ -230,10 +229,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
                     .ok_or(Error::UniswapV2CoreERC20UnderFlow4)
                     .unwrap_or_revert(),
             );
-            let address_0: Key = Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap();
+            let address_0: Key = null_key();
             self.emit(&ERC20Event::Transfer {
                 from: recipient,
                 to: address_0,
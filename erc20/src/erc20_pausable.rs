@@ -0,0 +1,50 @@
+use crate::erc20::{Error, ERC20};
+use casper_contract::contract_api::runtime;
+use casper_types::{ApiError, Key, U256};
+use casperlabs_contract_utils::ContractStorage;
+use casperlabs_pausable::PAUSABLE;
+
+/// Pause scopes checked in addition to the contract-wide pause.
+pub const TRANSFER_SCOPE: &str = "transfer";
+pub const MINT_SCOPE: &str = "mint";
+pub const BURN_SCOPE: &str = "burn";
+pub const APPROVE_SCOPE: &str = "approve";
+
+/// Pause-checked variants of the `ERC20` mutators. They do not override the `ERC20`
+/// methods, so a pausable contract must wire its entry points to the `pausable_*` ones.
+pub trait ERC20PAUSABLE<Storage: ContractStorage>: ERC20<Storage> + PAUSABLE<Storage> {
+    fn pausable_transfer(&mut self, recipient: Key, amount: U256) -> Result<(), u32> {
+        if self.is_scope_paused(TRANSFER_SCOPE) {
+            return Err(Error::ContractPaused as u32);
+        }
+        ERC20::transfer(self, recipient, amount)
+    }
+    fn pausable_transfer_from(
+        &mut self,
+        owner: Key,
+        recipient: Key,
+        amount: U256,
+    ) -> Result<(), u32> {
+        if self.is_scope_paused(TRANSFER_SCOPE) {
+            return Err(Error::ContractPaused as u32);
+        }
+        ERC20::transfer_from(self, owner, recipient, amount)
+    }
+    fn pausable_approve(&mut self, spender: Key, amount: U256) {
+        self._when_not_paused(APPROVE_SCOPE);
+        ERC20::approve(self, spender, amount);
+    }
+    fn pausable_mint(&mut self, recipient: Key, amount: U256) {
+        self._when_not_paused(MINT_SCOPE);
+        ERC20::mint(self, recipient, amount);
+    }
+    fn pausable_burn(&mut self, recipient: Key, amount: U256) {
+        self._when_not_paused(BURN_SCOPE);
+        ERC20::burn(self, recipient, amount);
+    }
+    fn _when_not_paused(&self, scope: &str) {
+        if self.is_scope_paused(scope) {
+            runtime::revert(ApiError::from(Error::ContractPaused));
+        }
+    }
+}
@@ -3,6 +3,10 @@ extern crate alloc;
 
 pub mod data;
 mod erc20;
+#[cfg(feature = "pausable")]
+mod erc20_pausable;
 
 pub use casperlabs_contract_utils;
 pub use erc20::{Error, ERC20};
+#[cfg(feature = "pausable")]
+pub use erc20_pausable::{APPROVE_SCOPE, BURN_SCOPE, ERC20PAUSABLE, MINT_SCOPE, TRANSFER_SCOPE};
@@ -1,19 +1,33 @@
+use crate::lp_token_wrapper::Error;
+use alloc::{string::ToString, vec::Vec};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 use casper_types::{bytesrepr::ToBytes, CLTyped, ContractHash, ContractPackageHash, Key, U256};
-use casperlabs_contract_utils::{get_key, set_key, Dict};
-
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+use casperlabs_contract_utils::{get_key, null_key, set_key, Dict};
+use core::convert::TryFrom;
 
 pub const UNI: &str = "uni";
+pub const TOKEN_INTERFACE: &str = "token_interface";
 pub const BALANCES: &str = "balances";
+pub const OPERATORS: &str = "operators";
+pub const LOCKED_BALANCES: &str = "locked_balances";
+pub const WEIGHTED_BALANCES: &str = "weighted_balances";
+pub const WEIGHTED_TOTAL_SUPPLY: &str = "weighted_total_supply";
+pub const POSITIONS: &str = "positions";
+pub const NEXT_POSITION_ID: &str = "next_position_id";
+pub const LOCK_SCHEDULE: &str = "lock_schedule";
+pub const PENALTY_SCHEDULE: &str = "penalty_schedule";
+pub const PENALTY_RECIPIENT: &str = "penalty_recipient";
+
+/// Multipliers and penalties are expressed in basis points.
+pub const BASIS_POINTS: u64 = 10_000;
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_CONTRACT_PACKAGE_HASH: &str = "self_contract_package_hash";
 pub const TOTAL_SUPPLY: &str = "total_supply";
 pub const RESULT: &str = "result";
 
 pub fn zero_address() -> Key {
-    Key::from_formatted_str(
-        "hash-0000000000000000000000000000000000000000000000000000000000000000".into(),
-    )
-    .unwrap()
+    null_key()
 }
 pub struct Balances {
     dict: Dict,
@@ -21,8 +35,12 @@ pub struct Balances {
 
 impl Balances {
     pub fn instance() -> Balances {
+        Balances::named(BALANCES)
+    }
+
+    pub fn named(name: &str) -> Balances {
         Balances {
-            dict: Dict::instance(BALANCES),
+            dict: Dict::instance(name),
         }
     }
 
@@ -30,6 +48,10 @@ impl Balances {
         Dict::init(BALANCES)
     }
 
+    pub fn init_named(name: &str) {
+        Dict::init(name)
+    }
+
     pub fn get(&self, owner: &Key) -> U256 {
         self.dict.get_by_key(owner).unwrap_or_default()
     }
@@ -38,12 +60,138 @@ impl Balances {
         self.dict.set_by_key(owner, value)
     }
 }
+pub struct Operators {
+    dict: Dict,
+}
+
+impl Operators {
+    pub fn instance() -> Operators {
+        Operators {
+            dict: Dict::instance(OPERATORS),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(OPERATORS)
+    }
+
+    pub fn get(&self, owner: &Key, operator: &Key) -> bool {
+        self.dict.get_by_keys((owner, operator)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, operator: &Key, approved: bool) {
+        self.dict.set_by_keys((owner, operator), approved);
+    }
+}
+#[derive(Clone, CLTyped, ToBytes, FromBytes)]
+pub struct Position {
+    pub id: U256,
+    pub owner: Key,
+    pub amount: U256,
+    pub unlock_time: u64,
+    pub multiplier: U256,
+    pub is_active: bool,
+}
+
+impl Position {
+    pub fn weighted_amount(&self) -> U256 {
+        self.amount
+            .checked_mul(self.multiplier)
+            .unwrap_or_revert_with(Error::LpTokenWrapperMultiplicationError)
+            / U256::from(BASIS_POINTS)
+    }
+}
+
+pub struct Positions {
+    dict: Dict,
+}
+
+impl Positions {
+    pub fn instance() -> Positions {
+        Positions {
+            dict: Dict::instance(POSITIONS),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(POSITIONS)
+    }
+
+    pub fn get(&self, id: &U256) -> Option<Position> {
+        self.dict.get(&id.to_string())
+    }
+
+    pub fn set(&self, position: &Position) {
+        self.dict.set(&position.id.to_string(), position.clone())
+    }
+}
 pub fn set_uni(uni: Key) {
     set_key(UNI, uni);
 }
 pub fn get_uni() -> Key {
     get_key(UNI).unwrap_or(zero_address())
 }
+
+/// Calling convention of the staked token's `transfer` / `transfer_from`.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TokenInterface {
+    /// `Key` arguments, returns `Result<(), u32>`.
+    Key = 0,
+    /// `Address` arguments, returns `()` (casper_erc20_new family).
+    Address = 1,
+}
+
+impl TryFrom<u8> for TokenInterface {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TokenInterface::Key),
+            1 => Ok(TokenInterface::Address),
+            _ => Err(Error::LpTokenWrapperInvalidTokenInterface),
+        }
+    }
+}
+
+pub fn set_token_interface(token_interface: TokenInterface) {
+    set_key(TOKEN_INTERFACE, token_interface as u8);
+}
+pub fn get_token_interface() -> TokenInterface {
+    TokenInterface::try_from(get_key::<u8>(TOKEN_INTERFACE).unwrap_or_default()).unwrap_or_revert()
+}
+pub fn set_weighted_total_supply(weighted_total_supply: U256) {
+    set_key(WEIGHTED_TOTAL_SUPPLY, weighted_total_supply);
+}
+pub fn get_weighted_total_supply() -> U256 {
+    get_key(WEIGHTED_TOTAL_SUPPLY).unwrap_or_default()
+}
+pub fn set_next_position_id(next_position_id: U256) {
+    set_key(NEXT_POSITION_ID, next_position_id);
+}
+pub fn get_next_position_id() -> U256 {
+    get_key(NEXT_POSITION_ID).unwrap_or_default()
+}
+/// `(lock_duration, multiplier)` pairs a staker may choose from.
+pub fn set_lock_schedule(lock_schedule: Vec<(u64, U256)>) {
+    set_key(LOCK_SCHEDULE, lock_schedule);
+}
+pub fn get_lock_schedule() -> Vec<(u64, U256)> {
+    get_key(LOCK_SCHEDULE).unwrap_or_default()
+}
+/// `(min_time_remaining, penalty)` pairs sorted by `min_time_remaining`.
+pub fn set_penalty_schedule(penalty_schedule: Vec<(u64, U256)>) {
+    set_key(PENALTY_SCHEDULE, penalty_schedule);
+}
+pub fn get_penalty_schedule() -> Vec<(u64, U256)> {
+    get_key(PENALTY_SCHEDULE).unwrap_or_default()
+}
+pub fn set_penalty_recipient(penalty_recipient: Key) {
+    set_key(PENALTY_RECIPIENT, penalty_recipient);
+}
+pub fn get_penalty_recipient() -> Option<Key> {
+    get_key(PENALTY_RECIPIENT)
+}
 pub fn set_total_supply(total_supply: U256) {
     set_key(TOTAL_SUPPLY, total_supply);
 }
@@ -4,5 +4,8 @@ extern crate alloc;
 
 pub mod data;
 mod lp_token_wrapper;
+mod safe_transfer;
 
-pub use lp_token_wrapper::LPTOKENWRAPPER;
+pub use data::{Position, TokenInterface};
+pub use lp_token_wrapper::{Error, LpTokenWrapperEvent, LPTOKENWRAPPER};
+pub use safe_transfer::{balance_of, safe_transfer, safe_transfer_from};
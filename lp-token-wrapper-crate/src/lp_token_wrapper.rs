@@ -1,9 +1,17 @@
 use crate::data::*;
-use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs, U256};
+use crate::safe_transfer::{safe_transfer, safe_transfer_from};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ContractHash, ContractPackageHash, Key, URef, U256};
 // use common::errors::*;
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
 use casper_types::ApiError;
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
+use casperlabs_reentrancy_guard::REENTRANCYGUARD;
 #[repr(u16)]
 pub enum Error {
     /// 65,540 for (Lp Token Wrapper Addition Error 1)
@@ -14,6 +22,30 @@ pub enum Error {
     LpTokenWrapperSubtractionError1 = 11903,
     /// 65,540 for (Lp Token Wrapper Subtraction Error 2)
     LpTokenWrapperSubtractionError2 = 11904,
+    /// 65,540 for (Lp Token Wrapper Transfer Failed)
+    LpTokenWrapperTransferFailed = 11905,
+    /// 65,540 for (Lp Token Wrapper Transfer From Failed)
+    LpTokenWrapperTransferFromFailed = 11906,
+    /// 65,540 for (Lp Token Wrapper Invalid Token Interface)
+    LpTokenWrapperInvalidTokenInterface = 11907,
+    /// 65,540 for (Lp Token Wrapper Caller Is Not Owner Or Operator)
+    LpTokenWrapperNotOwnerOrOperator = 11908,
+    /// 65,540 for (Lp Token Wrapper Invalid Lock Duration)
+    LpTokenWrapperInvalidLockDuration = 11909,
+    /// 65,540 for (Lp Token Wrapper Position Not Found)
+    LpTokenWrapperPositionNotFound = 11910,
+    /// 65,540 for (Lp Token Wrapper Position Closed)
+    LpTokenWrapperPositionClosed = 11911,
+    /// 65,540 for (Lp Token Wrapper Invalid Multiplier)
+    LpTokenWrapperInvalidMultiplier = 11912,
+    /// 65,540 for (Lp Token Wrapper Invalid Penalty)
+    LpTokenWrapperInvalidPenalty = 11913,
+    /// 65,540 for (Lp Token Wrapper Multiplication Error)
+    LpTokenWrapperMultiplicationError = 11914,
+    /// 65,540 for (Lp Token Wrapper Penalty Schedule Not Sorted)
+    LpTokenWrapperPenaltyScheduleNotSorted = 11915,
+    /// 65,540 for (Lp Token Wrapper Penalty Recipient Not Set)
+    LpTokenWrapperPenaltyRecipientNotSet = 11916,
 }
 
 impl From<Error> for ApiError {
@@ -22,12 +54,114 @@ impl From<Error> for ApiError {
     }
 }
 
-pub trait LPTOKENWRAPPER<Storage: ContractStorage>: ContractContext<Storage> {
+pub enum LpTokenWrapperEvent {
+    Staked {
+        payer: Key,
+        user: Key,
+        amount: U256,
+    },
+    Withdrawn {
+        user: Key,
+        recipient: Key,
+        amount: U256,
+    },
+    OperatorApproval {
+        owner: Key,
+        operator: Key,
+        approved: bool,
+    },
+    PositionOpened {
+        id: U256,
+        user: Key,
+        amount: U256,
+        unlock_time: u64,
+        multiplier: U256,
+    },
+    PositionWithdrawn {
+        id: U256,
+        user: Key,
+        amount: U256,
+        penalty: U256,
+    },
+}
+
+impl LpTokenWrapperEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            LpTokenWrapperEvent::Staked { .. } => "Staked",
+            LpTokenWrapperEvent::Withdrawn { .. } => "Withdrawn",
+            LpTokenWrapperEvent::OperatorApproval { .. } => "OperatorApproval",
+            LpTokenWrapperEvent::PositionOpened { .. } => "PositionOpened",
+            LpTokenWrapperEvent::PositionWithdrawn { .. } => "PositionWithdrawn",
+        }
+        .to_string()
+    }
+}
+
+fn blocktime() -> u64 {
+    runtime::get_blocktime().into()
+}
+
+fn add_to(balances: &Balances, account: &Key, amount: U256) {
+    balances.set(
+        account,
+        balances
+            .get(account)
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError2),
+    );
+}
+
+fn sub_from(balances: &Balances, account: &Key, amount: U256) {
+    balances.set(
+        account,
+        balances
+            .get(account)
+            .checked_sub(amount)
+            .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError2),
+    );
+}
+
+fn add_weighted(account: &Key, amount: U256) {
+    set_weighted_total_supply(
+        get_weighted_total_supply()
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError1),
+    );
+    add_to(&Balances::named(WEIGHTED_BALANCES), account, amount);
+}
+
+fn sub_weighted(account: &Key, amount: U256) {
+    set_weighted_total_supply(
+        get_weighted_total_supply()
+            .checked_sub(amount)
+            .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError1),
+    );
+    sub_from(&Balances::named(WEIGHTED_BALANCES), account, amount);
+}
+
+pub trait LPTOKENWRAPPER<Storage: ContractStorage>:
+    ContractContext<Storage> + REENTRANCYGUARD<Storage>
+{
     fn init(&self, uni: Key, contract_hash: ContractHash, package_hash: ContractPackageHash) {
+        self.init_with_token_interface(uni, TokenInterface::Key, contract_hash, package_hash);
+    }
+    fn init_with_token_interface(
+        &self,
+        uni: Key,
+        token_interface: TokenInterface,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
         set_uni(uni);
+        set_token_interface(token_interface);
         set_hash(contract_hash);
         set_package_hash(package_hash);
         Balances::init();
+        Balances::init_named(LOCKED_BALANCES);
+        Balances::init_named(WEIGHTED_BALANCES);
+        Operators::init();
+        Positions::init();
     }
     fn total_supply(&self) -> U256 {
         return get_total_supply();
@@ -35,51 +169,333 @@ pub trait LPTOKENWRAPPER<Storage: ContractStorage>: ContractContext<Storage> {
     fn balance_of(&self, account: Key) -> U256 {
         return Balances::instance().get(&account);
     }
+    fn locked_balance_of(&self, account: Key) -> U256 {
+        Balances::named(LOCKED_BALANCES).get(&account)
+    }
+    /// Staked amounts scaled by their lock multipliers; use these for reward accounting.
+    fn weighted_total_supply(&self) -> U256 {
+        get_weighted_total_supply()
+    }
+    fn weighted_balance_of(&self, account: Key) -> U256 {
+        Balances::named(WEIGHTED_BALANCES).get(&account)
+    }
+    fn position(&self, id: U256) -> Position {
+        Positions::instance()
+            .get(&id)
+            .unwrap_or_revert_with(Error::LpTokenWrapperPositionNotFound)
+    }
+    fn lock_schedule(&self) -> Vec<(u64, U256)> {
+        get_lock_schedule()
+    }
+    fn penalty_schedule(&self) -> Vec<(u64, U256)> {
+        get_penalty_schedule()
+    }
+    fn penalty_recipient(&self) -> Option<Key> {
+        get_penalty_recipient()
+    }
+    // Internal, the implementing contract exposes these setters behind its own access control.
+    fn _set_lock_schedule(&mut self, lock_schedule: Vec<(u64, U256)>) {
+        for (_, multiplier) in lock_schedule.iter() {
+            if *multiplier < U256::from(BASIS_POINTS) {
+                runtime::revert(ApiError::from(Error::LpTokenWrapperInvalidMultiplier));
+            }
+        }
+        set_lock_schedule(lock_schedule);
+    }
+    fn _set_penalty_schedule(&mut self, penalty_schedule: Vec<(u64, U256)>) {
+        let charges_penalty = penalty_schedule
+            .iter()
+            .any(|(_, penalty)| !penalty.is_zero());
+        if charges_penalty && get_penalty_recipient().is_none() {
+            runtime::revert(ApiError::from(Error::LpTokenWrapperPenaltyRecipientNotSet));
+        }
+        for (index, (min_time_remaining, penalty)) in penalty_schedule.iter().enumerate() {
+            if *penalty > U256::from(BASIS_POINTS) {
+                runtime::revert(ApiError::from(Error::LpTokenWrapperInvalidPenalty));
+            }
+            if index > 0 && penalty_schedule[index - 1].0 >= *min_time_remaining {
+                runtime::revert(ApiError::from(
+                    Error::LpTokenWrapperPenaltyScheduleNotSorted,
+                ));
+            }
+        }
+        set_penalty_schedule(penalty_schedule);
+    }
+    /// Pass the null key to keep penalties locked in the contract instead of paying
+    /// them out; nothing is ever sent to the null key.
+    fn _set_penalty_recipient(&mut self, penalty_recipient: Key) {
+        set_penalty_recipient(penalty_recipient);
+    }
+    /// Penalty charged if position `id` were withdrawn now.
+    fn penalty_of(&self, id: U256) -> U256 {
+        let position = self.position(id);
+        let now = blocktime();
+        if now >= position.unlock_time {
+            return U256::zero();
+        }
+        let time_remaining = position.unlock_time - now;
+        let penalty = get_penalty_schedule()
+            .into_iter()
+            .take_while(|(min_time_remaining, _)| *min_time_remaining <= time_remaining)
+            .last()
+            .map(|(_, penalty)| penalty)
+            .unwrap_or_default();
+        position
+            .amount
+            .checked_mul(penalty)
+            .unwrap_or_revert_with(Error::LpTokenWrapperMultiplicationError)
+            / U256::from(BASIS_POINTS)
+    }
+    fn is_operator(&self, owner: Key, operator: Key) -> bool {
+        Operators::instance().get(&owner, &operator)
+    }
+    fn set_operator(&mut self, operator: Key, approved: bool) {
+        let owner = self.get_caller();
+        Operators::instance().set(&owner, &operator, approved);
+        self.lp_token_wrapper_emit(&LpTokenWrapperEvent::OperatorApproval {
+            owner,
+            operator,
+            approved,
+        });
+    }
     fn stake(&mut self, amount: U256) {
-        set_total_supply(
-            get_total_supply()
-                .checked_add(amount)
-                .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError1),
-        );
-        Balances::instance().set(
-            &self.get_caller(),
-            Balances::instance()
-                .get(&self.get_caller())
-                .checked_add(amount)
-                .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError2),
-        );
-        let _ret: Result<(), u32> = runtime::call_versioned_contract(
-            get_uni().into_hash().unwrap_or_revert().into(),
-            None,
-            "transfer_from",
-            runtime_args! {
-                "owner" => self.get_caller(),
-                "recipient" => Key::from(get_package_hash()),
-                "amount" => amount
-            },
-        );
+        self._stake(self.get_caller(), self.get_caller(), amount);
+    }
+    fn stake_for(&mut self, beneficiary: Key, amount: U256) {
+        self._stake(self.get_caller(), beneficiary, amount);
     }
     fn withdraw(&mut self, amount: U256) {
-        set_total_supply(
-            get_total_supply()
-                .checked_sub(amount)
-                .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError1),
-        );
-        Balances::instance().set(
-            &self.get_caller(),
-            Balances::instance()
-                .get(&self.get_caller())
-                .checked_sub(amount)
-                .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError2),
-        );
-        let _ret: Result<(), u32> = runtime::call_versioned_contract(
-            get_uni().into_hash().unwrap_or_revert().into(),
-            None,
-            "transfer",
-            runtime_args! {
-                "recipient" => self.get_caller(),
-                "amount" => amount
-            },
-        );
+        self._withdraw(self.get_caller(), self.get_caller(), amount);
+    }
+    fn withdraw_for(&mut self, owner: Key, recipient: Key, amount: U256) {
+        let caller = self.get_caller();
+        if caller != owner && !Operators::instance().get(&owner, &caller) {
+            runtime::revert(ApiError::from(Error::LpTokenWrapperNotOwnerOrOperator));
+        }
+        self._withdraw(owner, recipient, amount);
+    }
+    fn stake_locked(&mut self, amount: U256, lock_duration: u64) -> U256 {
+        let multiplier = get_lock_schedule()
+            .into_iter()
+            .find(|(duration, _)| *duration == lock_duration)
+            .map(|(_, multiplier)| multiplier)
+            .unwrap_or_revert_with(Error::LpTokenWrapperInvalidLockDuration);
+        let owner = self.get_caller();
+        let position = self.non_reentrant(|_| {
+            safe_transfer_from(
+                get_uni(),
+                get_token_interface(),
+                owner,
+                Key::from(get_package_hash()),
+                amount,
+            );
+            let id = get_next_position_id();
+            set_next_position_id(id + 1);
+            let position = Position {
+                id,
+                owner,
+                amount,
+                unlock_time: blocktime()
+                    .checked_add(lock_duration)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError1),
+                multiplier,
+                is_active: true,
+            };
+            Positions::instance().set(&position);
+            set_total_supply(
+                get_total_supply()
+                    .checked_add(amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError1),
+            );
+            add_to(&Balances::named(LOCKED_BALANCES), &owner, amount);
+            add_weighted(&owner, position.weighted_amount());
+            position
+        });
+        self.lp_token_wrapper_emit(&LpTokenWrapperEvent::PositionOpened {
+            id: position.id,
+            user: owner,
+            amount,
+            unlock_time: position.unlock_time,
+            multiplier,
+        });
+        position.id
+    }
+    fn withdraw_position(&mut self, id: U256) {
+        let mut position = self.position(id);
+        let caller = self.get_caller();
+        if caller != position.owner && !Operators::instance().get(&position.owner, &caller) {
+            runtime::revert(ApiError::from(Error::LpTokenWrapperNotOwnerOrOperator));
+        }
+        if !position.is_active {
+            runtime::revert(ApiError::from(Error::LpTokenWrapperPositionClosed));
+        }
+        let penalty = self.penalty_of(id);
+        self.non_reentrant(|_| {
+            position.is_active = false;
+            Positions::instance().set(&position);
+            set_total_supply(
+                get_total_supply()
+                    .checked_sub(position.amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError1),
+            );
+            sub_from(
+                &Balances::named(LOCKED_BALANCES),
+                &position.owner,
+                position.amount,
+            );
+            sub_weighted(&position.owner, position.weighted_amount());
+            let interface = get_token_interface();
+            safe_transfer(
+                get_uni(),
+                interface,
+                position.owner,
+                position.amount - penalty,
+            );
+            if !penalty.is_zero() {
+                let penalty_recipient = get_penalty_recipient()
+                    .unwrap_or_revert_with(Error::LpTokenWrapperPenaltyRecipientNotSet);
+                if !is_null_key(&penalty_recipient) {
+                    safe_transfer(get_uni(), interface, penalty_recipient, penalty);
+                }
+            }
+        });
+        self.lp_token_wrapper_emit(&LpTokenWrapperEvent::PositionWithdrawn {
+            id,
+            user: position.owner,
+            amount: position.amount,
+            penalty,
+        });
+    }
+    fn _stake(&mut self, payer: Key, beneficiary: Key, amount: U256) {
+        self.non_reentrant(|_| {
+            safe_transfer_from(
+                get_uni(),
+                get_token_interface(),
+                payer,
+                Key::from(get_package_hash()),
+                amount,
+            );
+            set_total_supply(
+                get_total_supply()
+                    .checked_add(amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError1),
+            );
+            Balances::instance().set(
+                &beneficiary,
+                Balances::instance()
+                    .get(&beneficiary)
+                    .checked_add(amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperAdditionError2),
+            );
+            add_weighted(&beneficiary, amount);
+        });
+        self.lp_token_wrapper_emit(&LpTokenWrapperEvent::Staked {
+            payer,
+            user: beneficiary,
+            amount,
+        });
+    }
+    fn _withdraw(&mut self, owner: Key, recipient: Key, amount: U256) {
+        self.non_reentrant(|_| {
+            set_total_supply(
+                get_total_supply()
+                    .checked_sub(amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError1),
+            );
+            Balances::instance().set(
+                &owner,
+                Balances::instance()
+                    .get(&owner)
+                    .checked_sub(amount)
+                    .unwrap_or_revert_with(Error::LpTokenWrapperSubtractionError2),
+            );
+            sub_weighted(&owner, amount);
+            safe_transfer(get_uni(), get_token_interface(), recipient, amount);
+        });
+        self.lp_token_wrapper_emit(&LpTokenWrapperEvent::Withdrawn {
+            user: owner,
+            recipient,
+            amount,
+        });
+    }
+    fn lp_token_wrapper_emit(&mut self, lp_token_wrapper_event: &LpTokenWrapperEvent) {
+        let mut events = Vec::new();
+        let package = get_package_hash();
+        match lp_token_wrapper_event {
+            LpTokenWrapperEvent::Staked {
+                payer,
+                user,
+                amount,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", lp_token_wrapper_event.type_name());
+                event.insert("payer", payer.to_string());
+                event.insert("user", user.to_string());
+                event.insert("amount", amount.to_string());
+                events.push(event);
+            }
+            LpTokenWrapperEvent::Withdrawn {
+                user,
+                recipient,
+                amount,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", lp_token_wrapper_event.type_name());
+                event.insert("user", user.to_string());
+                event.insert("recipient", recipient.to_string());
+                event.insert("amount", amount.to_string());
+                events.push(event);
+            }
+            LpTokenWrapperEvent::OperatorApproval {
+                owner,
+                operator,
+                approved,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", lp_token_wrapper_event.type_name());
+                event.insert("owner", owner.to_string());
+                event.insert("operator", operator.to_string());
+                event.insert("approved", approved.to_string());
+                events.push(event);
+            }
+            LpTokenWrapperEvent::PositionOpened {
+                id,
+                user,
+                amount,
+                unlock_time,
+                multiplier,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", lp_token_wrapper_event.type_name());
+                event.insert("id", id.to_string());
+                event.insert("user", user.to_string());
+                event.insert("amount", amount.to_string());
+                event.insert("unlock_time", unlock_time.to_string());
+                event.insert("multiplier", multiplier.to_string());
+                events.push(event);
+            }
+            LpTokenWrapperEvent::PositionWithdrawn {
+                id,
+                user,
+                amount,
+                penalty,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", lp_token_wrapper_event.type_name());
+                event.insert("id", id.to_string());
+                event.insert("user", user.to_string());
+                event.insert("amount", amount.to_string());
+                event.insert("penalty", penalty.to_string());
+                events.push(event);
+            }
+        };
+        for event in events {
+            let _: URef = storage::new_uref(event);
+        }
     }
 }
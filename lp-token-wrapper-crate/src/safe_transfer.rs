@@ -0,0 +1,95 @@
+use crate::data::TokenInterface;
+use crate::lp_token_wrapper::Error;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20_crate::Address;
+use casper_types::{runtime_args, Key, RuntimeArgs, U256};
+
+// Tokens of the casper_erc20_new family take `Address` arguments and revert on
+// failure instead of returning a `Result`, so only the `Key` family is checked.
+pub fn safe_transfer(token: Key, interface: TokenInterface, recipient: Key, amount: U256) {
+    let token_hash = token.into_hash().unwrap_or_revert().into();
+    match interface {
+        TokenInterface::Key => {
+            let ret: Result<(), u32> = runtime::call_versioned_contract(
+                token_hash,
+                None,
+                "transfer",
+                runtime_args! {
+                    "recipient" => recipient,
+                    "amount" => amount
+                },
+            );
+            ret.unwrap_or_revert_with(Error::LpTokenWrapperTransferFailed);
+        }
+        TokenInterface::Address => {
+            runtime::call_versioned_contract::<()>(
+                token_hash,
+                None,
+                "transfer",
+                runtime_args! {
+                    "recipient" => Address::from(recipient),
+                    "amount" => amount
+                },
+            );
+        }
+    }
+}
+
+pub fn balance_of(token: Key, interface: TokenInterface, owner: Key) -> U256 {
+    let token_hash = token.into_hash().unwrap_or_revert().into();
+    match interface {
+        TokenInterface::Key => runtime::call_versioned_contract(
+            token_hash,
+            None,
+            "balance_of",
+            runtime_args! {
+                "owner" => owner
+            },
+        ),
+        TokenInterface::Address => runtime::call_versioned_contract(
+            token_hash,
+            None,
+            "balance_of",
+            runtime_args! {
+                "address" => Address::from(owner)
+            },
+        ),
+    }
+}
+
+pub fn safe_transfer_from(
+    token: Key,
+    interface: TokenInterface,
+    owner: Key,
+    recipient: Key,
+    amount: U256,
+) {
+    let token_hash = token.into_hash().unwrap_or_revert().into();
+    match interface {
+        TokenInterface::Key => {
+            let ret: Result<(), u32> = runtime::call_versioned_contract(
+                token_hash,
+                None,
+                "transfer_from",
+                runtime_args! {
+                    "owner" => owner,
+                    "recipient" => recipient,
+                    "amount" => amount
+                },
+            );
+            ret.unwrap_or_revert_with(Error::LpTokenWrapperTransferFromFailed);
+        }
+        TokenInterface::Address => {
+            runtime::call_versioned_contract::<()>(
+                token_hash,
+                None,
+                "transfer_from",
+                runtime_args! {
+                    "owner" => Address::from(owner),
+                    "recipient" => Address::from(recipient),
+                    "amount" => amount
+                },
+            );
+        }
+    }
+}
@@ -3,19 +3,21 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{bytesrepr::ToBytes, CLTyped, ContractHash, ContractPackageHash, Key};
-use casperlabs_contract_utils::{get_key, set_key};
+use casperlabs_contract_utils::{get_key, null_key, set_key};
 use core::convert::TryInto;
 
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_CONTRACT_PACKAGE_HASH: &str = "self_contract_package_hash";
 pub const RESULT: &str = "result";
 pub const OWNER: &str = "owner";
+pub const PENDING_OWNER: &str = "pending_owner";
+pub const PENDING_OWNER_EXPIRY: &str = "pending_owner_expiry";
+// Where `OWNED` kept its nominee before sharing state with `OWNABLE`, still read by
+// contracts upgraded from that layout until the first transfer writes `PENDING_OWNER`.
+pub const LEGACY_NOMINATED_OWNER: &str = "nominated_owner";
 //Zero Address
 pub fn zero_address() -> Key {
-    Key::from_formatted_str(
-        "hash-0000000000000000000000000000000000000000000000000000000000000000".into(),
-    )
-    .unwrap()
+    null_key()
 }
 
 pub fn set_result<T: ToBytes + CLTyped>(value: T) {
@@ -36,6 +38,20 @@ pub fn set_owner(owner: Key) {
 pub fn get_owner() -> Key {
     get_key(OWNER).unwrap_or(zero_address())
 }
+pub fn set_pending_owner(pending_owner: Key) {
+    set_key(PENDING_OWNER, pending_owner);
+}
+pub fn get_pending_owner() -> Key {
+    get_key(PENDING_OWNER)
+        .or_else(|| get_key(LEGACY_NOMINATED_OWNER))
+        .unwrap_or(zero_address())
+}
+pub fn set_pending_owner_expiry(expires_at: Option<u64>) {
+    set_key(PENDING_OWNER_EXPIRY, expires_at);
+}
+pub fn get_pending_owner_expiry() -> Option<u64> {
+    get_key(PENDING_OWNER_EXPIRY).unwrap_or_default()
+}
 
 pub fn set_hash(contract_hash: ContractHash) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
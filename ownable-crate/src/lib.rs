@@ -4,5 +4,6 @@ extern crate alloc;
 
 pub mod data;
 mod ownable;
+pub mod ownership;
 
-pub use ownable::OWNABLE;
+pub use ownable::{Error, OWNABLE};
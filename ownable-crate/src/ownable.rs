@@ -1,19 +1,26 @@
 use crate::alloc::string::ToString;
 use crate::data::{self};
+use crate::ownership::{self, AcceptError};
 use alloc::collections::BTreeMap;
 use alloc::{string::String, vec::Vec};
 use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
 use casper_types::{ApiError, ContractHash, ContractPackageHash, Key, URef};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
+use core::convert::TryFrom;
 //Events
 
 #[repr(u16)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     /// 65,546 for (Ownable: caller is not the owner)
     OwnableNotOwner = 11501,
     /// 65,540 for (Ownable: new owner is the zero address)
     OwnableNewOwnerAddressZero = 11502,
+    /// 65,540 for (Ownable: caller is not the pending owner)
+    OwnableNotPendingOwner = 11503,
+    /// 65,540 for (Ownable: ownership transfer expired)
+    OwnableTransferExpired = 11504,
 }
 
 impl From<Error> for ApiError {
@@ -22,8 +29,34 @@ impl From<Error> for ApiError {
     }
 }
 
+impl TryFrom<u16> for Error {
+    type Error = ();
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            11501 => Ok(Error::OwnableNotOwner),
+            11502 => Ok(Error::OwnableNewOwnerAddressZero),
+            11503 => Ok(Error::OwnableNotPendingOwner),
+            11504 => Ok(Error::OwnableTransferExpired),
+            _ => Err(()),
+        }
+    }
+}
+
 pub enum OwnableEvent {
-    OwnershipTransferred { previous_owner: Key, new_owner: Key },
+    OwnershipTransferred {
+        previous_owner: Key,
+        new_owner: Key,
+    },
+    OwnershipTransferStarted {
+        previous_owner: Key,
+        new_owner: Key,
+        expires_at: Option<u64>,
+    },
+    OwnershipTransferCancelled {
+        owner: Key,
+        pending_owner: Key,
+    },
 }
 
 impl OwnableEvent {
@@ -33,6 +66,8 @@ impl OwnableEvent {
                 previous_owner: _,
                 new_owner: _,
             } => "OwnershipTransferred",
+            OwnableEvent::OwnershipTransferStarted { .. } => "OwnershipTransferStarted",
+            OwnableEvent::OwnershipTransferCancelled { .. } => "OwnershipTransferCancelled",
         }
         .to_string()
     }
@@ -51,6 +86,13 @@ pub trait OWNABLE<Storage: ContractStorage>: ContractContext<Storage> {
     fn owner(&self) -> Key {
         return data::get_owner();
     }
+    // @dev Returns the address of the pending owner, or the zero address if none.
+    fn pending_owner(&self) -> Key {
+        data::get_pending_owner()
+    }
+    fn pending_owner_expiry(&self) -> Option<u64> {
+        data::get_pending_owner_expiry()
+    }
     // @dev Throws if called by any account other than the owner.
     fn only_owner(&self) {
         if !(self.is_owner()) {
@@ -68,6 +110,7 @@ pub trait OWNABLE<Storage: ContractStorage>: ContractContext<Storage> {
     // * thereby removing any functionality that is only available to the owner.
     fn renounce_ownership(&mut self) {
         self.only_owner();
+        ownership::cancel_transfer();
         self.ownable_emit(&OwnableEvent::OwnershipTransferred {
             previous_owner: data::get_owner(),
             new_owner: data::zero_address(),
@@ -80,10 +123,59 @@ pub trait OWNABLE<Storage: ContractStorage>: ContractContext<Storage> {
         self.only_owner();
         self._transfer_ownership(new_owner);
     }
+    // * @dev Starts the ownership transfer of the contract to a new account (`newOwner`).
+    // * The new owner must call `accept_ownership` to complete it.
+    // * Can only be called by the current owner.
+    fn begin_ownership_transfer(&mut self, new_owner: Key) {
+        self.begin_ownership_transfer_with_expiry(new_owner, None);
+    }
+    // * @dev Same as `begin_ownership_transfer`, but the offer can no longer be
+    // * accepted after the block time `expires_at`.
+    fn begin_ownership_transfer_with_expiry(&mut self, new_owner: Key, expires_at: Option<u64>) {
+        self.only_owner();
+        if is_null_key(&new_owner) {
+            runtime::revert(ApiError::from(Error::OwnableNewOwnerAddressZero));
+        }
+        if ownership::start_transfer(new_owner, expires_at).is_err() {
+            runtime::revert(ApiError::from(Error::OwnableTransferExpired));
+        }
+        self.ownable_emit(&OwnableEvent::OwnershipTransferStarted {
+            previous_owner: data::get_owner(),
+            new_owner,
+            expires_at,
+        });
+    }
+    // * @dev The pending owner accepts the ownership transfer.
+    fn accept_ownership(&mut self) {
+        match ownership::accept_transfer(self.get_caller()) {
+            Ok((previous_owner, new_owner)) => {
+                self.ownable_emit(&OwnableEvent::OwnershipTransferred {
+                    previous_owner,
+                    new_owner,
+                });
+            }
+            Err(AcceptError::NotPendingOwner) => {
+                runtime::revert(ApiError::from(Error::OwnableNotPendingOwner))
+            }
+            Err(AcceptError::Expired) => {
+                runtime::revert(ApiError::from(Error::OwnableTransferExpired))
+            }
+        }
+    }
+    // * @dev Cancels a pending ownership transfer. Can only be called by the current owner.
+    fn cancel_ownership_transfer(&mut self) {
+        self.only_owner();
+        let pending_owner = ownership::cancel_transfer();
+        self.ownable_emit(&OwnableEvent::OwnershipTransferCancelled {
+            owner: data::get_owner(),
+            pending_owner,
+        });
+    }
     fn _transfer_ownership(&mut self, new_owner: Key) {
-        if !(new_owner != data::zero_address()) {
+        if is_null_key(&new_owner) {
             runtime::revert(ApiError::from(Error::OwnableNewOwnerAddressZero));
         }
+        ownership::cancel_transfer();
         self.ownable_emit(&OwnableEvent::OwnershipTransferred {
             previous_owner: data::get_owner(),
             new_owner: new_owner,
@@ -106,6 +198,32 @@ pub trait OWNABLE<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("new_owner", new_owner.to_string());
                 events.push(event);
             }
+            OwnableEvent::OwnershipTransferStarted {
+                previous_owner,
+                new_owner,
+                expires_at,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", ownable_event.type_name());
+                event.insert("previous_owner", previous_owner.to_string());
+                event.insert("new_owner", new_owner.to_string());
+                if let Some(expires_at) = expires_at {
+                    event.insert("expires_at", expires_at.to_string());
+                }
+                events.push(event);
+            }
+            OwnableEvent::OwnershipTransferCancelled {
+                owner,
+                pending_owner,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", ownable_event.type_name());
+                event.insert("owner", owner.to_string());
+                event.insert("pending_owner", pending_owner.to_string());
+                events.push(event);
+            }
         };
         for event in events {
             let _: URef = storage::new_uref(event);
@@ -0,0 +1,53 @@
+//! Two-step ownership state shared by `OWNABLE` and `OWNED`, so both traits
+//! read and write the same `owner` / `pending_owner` keys and follow the same
+//! accept and expiry rules.
+use crate::data;
+use casper_contract::contract_api::runtime;
+use casper_types::Key;
+use casperlabs_contract_utils::is_null_key;
+
+pub enum AcceptError {
+    NotPendingOwner,
+    Expired,
+}
+
+/// Offers ownership to `new_owner`, refusing an expiry that is not after the
+/// current block time since such an offer could never be accepted.
+pub fn start_transfer(new_owner: Key, expires_at: Option<u64>) -> Result<(), AcceptError> {
+    if let Some(expires_at) = expires_at {
+        let now: u64 = runtime::get_blocktime().into();
+        if expires_at <= now {
+            return Err(AcceptError::Expired);
+        }
+    }
+    data::set_pending_owner(new_owner);
+    data::set_pending_owner_expiry(expires_at);
+    Ok(())
+}
+
+/// Clears the pending transfer and returns the owner it was pending for.
+pub fn cancel_transfer() -> Key {
+    let pending_owner = data::get_pending_owner();
+    data::set_pending_owner(data::zero_address());
+    data::set_pending_owner_expiry(None);
+    pending_owner
+}
+
+/// Completes the pending transfer if `caller` is the pending owner and the
+/// offer has not expired. Returns `(previous_owner, new_owner)`.
+pub fn accept_transfer(caller: Key) -> Result<(Key, Key), AcceptError> {
+    let pending_owner = data::get_pending_owner();
+    if is_null_key(&pending_owner) || caller != pending_owner {
+        return Err(AcceptError::NotPendingOwner);
+    }
+    if let Some(expires_at) = data::get_pending_owner_expiry() {
+        let now: u64 = runtime::get_blocktime().into();
+        if now > expires_at {
+            return Err(AcceptError::Expired);
+        }
+    }
+    let previous_owner = data::get_owner();
+    data::set_owner(pending_owner);
+    cancel_transfer();
+    Ok((previous_owner, pending_owner))
+}
@@ -3,20 +3,24 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{bytesrepr::ToBytes, CLTyped, ContractPackageHash, Key};
-use casperlabs_contract_utils::{get_key, set_key};
+use casperlabs_contract_utils::{get_key, null_key, set_key};
+use casperlabs_ownable::data as ownable;
 use core::convert::TryInto;
 
-pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
+// Stored as a `Key`, so it must not share `self_contract_hash` with crates such as
+// `OWNABLE` or `LPTOKENWRAPPER` that keep a `ContractHash` under that name.
+pub const SELF_CONTRACT_HASH: &str = "owned_contract_hash";
+// Where `OWNED` kept its hash before the rename, still read by contracts upgraded
+// from that layout.
+pub const LEGACY_SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_PACKAGE_HASH: &str = "self_package_hash";
-pub const OWNER: &str = "owner";
-pub const NOMINATED_OWNER: &str = "nominated_owner";
+// Shared with `OWNABLE` so both traits see the same ownership state.
+pub const OWNER: &str = ownable::OWNER;
+pub const NOMINATED_OWNER: &str = ownable::PENDING_OWNER;
 pub const RESULT: &str = "result";
 //Zero Address
 pub fn zero_address() -> Key {
-    Key::from_formatted_str(
-        "hash-0000000000000000000000000000000000000000000000000000000000000000".into(),
-    )
-    .unwrap()
+    null_key()
 }
 pub fn set_result<T: ToBytes + CLTyped>(value: T) {
     match runtime::get_key(RESULT) {
@@ -31,23 +35,25 @@ pub fn set_result<T: ToBytes + CLTyped>(value: T) {
     }
 }
 pub fn set_owner(owner: Key) {
-    set_key(OWNER, owner);
+    ownable::set_owner(owner);
 }
 pub fn get_owner() -> Key {
-    get_key(OWNER).unwrap_or_revert()
+    ownable::get_owner()
 }
 pub fn set_nominated_owner(nominated_owner: Key) {
-    set_key(NOMINATED_OWNER, nominated_owner);
+    ownable::set_pending_owner(nominated_owner);
 }
 pub fn get_nominated_owner() -> Key {
-    get_key(NOMINATED_OWNER).unwrap_or_revert()
+    ownable::get_pending_owner()
 }
 pub fn set_hash(contract_hash: Key) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
 }
 
 pub fn get_hash() -> Key {
-    get_key(SELF_CONTRACT_HASH).unwrap_or_revert()
+    get_key(SELF_CONTRACT_HASH)
+        .or_else(|| get_key(LEGACY_SELF_CONTRACT_HASH))
+        .unwrap_or_revert()
 }
 
 pub fn set_package_hash(package_hash: ContractPackageHash) {
@@ -5,4 +5,4 @@ extern crate alloc;
 pub mod data;
 mod owned;
 
-pub use owned::OWNED;
+pub use owned::{Error, OWNED};
@@ -5,10 +5,13 @@ use alloc::{string::String, vec::Vec};
 use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
 use casper_types::{ApiError, ContractPackageHash, Key, URef};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
+use casperlabs_ownable::ownership::{self, AcceptError};
+use core::convert::TryFrom;
 
 //Errors
 #[repr(u16)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     /// Owner address cannot be 0
     OwnerAddressZero = 20001,
@@ -16,6 +19,8 @@ pub enum Error {
     NominatedBeforeAccept = 20002,
     /// Only the contract owner may perform this action
     OnlyContractOwner = 20003,
+    /// The nomination has expired
+    NominationExpired = 20004,
 }
 
 impl From<Error> for ApiError {
@@ -23,10 +28,25 @@ impl From<Error> for ApiError {
         ApiError::User(error as u16)
     }
 }
+
+impl TryFrom<u16> for Error {
+    type Error = ();
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            20001 => Ok(Error::OwnerAddressZero),
+            20002 => Ok(Error::NominatedBeforeAccept),
+            20003 => Ok(Error::OnlyContractOwner),
+            20004 => Ok(Error::NominationExpired),
+            _ => Err(()),
+        }
+    }
+}
 //Events
 pub enum OwnedEvent {
     OwnerNominated { new_owner: Key },
     OwnerChanged { old_owner: Key, new_owner: Key },
+    NominationCancelled { nominated_owner: Key },
 }
 
 impl OwnedEvent {
@@ -36,14 +56,15 @@ impl OwnedEvent {
             OwnedEvent::OwnerChanged {
                 old_owner: _,
                 new_owner: _,
-            } => "OwnerNominated",
+            } => "OwnerChanged",
+            OwnedEvent::NominationCancelled { nominated_owner: _ } => "NominationCancelled",
         }
         .to_string()
     }
 }
 pub trait OWNED<Storage: ContractStorage>: ContractContext<Storage> {
     fn init(&mut self, owner: Key, contract_hash: Key, package_hash: ContractPackageHash) {
-        if !(owner != data::zero_address()) {
+        if is_null_key(&owner) {
             runtime::revert(ApiError::from(Error::OwnerAddressZero));
         }
         data::set_owner(owner);
@@ -54,21 +75,49 @@ pub trait OWNED<Storage: ContractStorage>: ContractContext<Storage> {
             new_owner: data::get_owner(),
         });
     }
+    fn nominated_owner(&self) -> Key {
+        data::get_nominated_owner()
+    }
     fn nominate_new_owner(&mut self, owner: Key) {
+        self.nominate_new_owner_with_expiry(owner, None);
+    }
+    fn nominate_new_owner_with_expiry(&mut self, owner: Key, expires_at: Option<u64>) {
         self.only_owner();
-        data::set_nominated_owner(owner);
+        if is_null_key(&owner) {
+            runtime::revert(ApiError::from(Error::OwnerAddressZero));
+        }
+        if ownership::start_transfer(owner, expires_at).is_err() {
+            runtime::revert(ApiError::from(Error::NominationExpired));
+        }
         self.owned_emit(&OwnedEvent::OwnerNominated { new_owner: owner });
     }
     fn accept_ownership(&mut self) {
-        if !(self.get_caller() == data::get_nominated_owner()) {
-            runtime::revert(ApiError::from(Error::NominatedBeforeAccept));
+        match ownership::accept_transfer(self.get_caller()) {
+            Ok((old_owner, new_owner)) => {
+                self.owned_emit(&OwnedEvent::OwnerChanged {
+                    old_owner,
+                    new_owner,
+                });
+            }
+            Err(AcceptError::NotPendingOwner) => {
+                runtime::revert(ApiError::from(Error::NominatedBeforeAccept))
+            }
+            Err(AcceptError::Expired) => runtime::revert(ApiError::from(Error::NominationExpired)),
         }
+    }
+    fn cancel_nomination(&mut self) {
+        self.only_owner();
+        let nominated_owner = ownership::cancel_transfer();
+        self.owned_emit(&OwnedEvent::NominationCancelled { nominated_owner });
+    }
+    fn renounce_ownership(&mut self) {
+        self.only_owner();
+        ownership::cancel_transfer();
         self.owned_emit(&OwnedEvent::OwnerChanged {
             old_owner: data::get_owner(),
-            new_owner: data::get_nominated_owner(),
+            new_owner: data::zero_address(),
         });
-        data::set_owner(data::get_nominated_owner());
-        data::set_nominated_owner(data::zero_address());
+        data::set_owner(data::zero_address());
     }
     fn only_owner(&self) {
         self._only_owner();
@@ -100,6 +149,13 @@ pub trait OWNED<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("new_owner", new_owner.to_string());
                 events.push(event);
             }
+            OwnedEvent::NominationCancelled { nominated_owner } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", owned_event.type_name());
+                event.insert("nominated_owner", nominated_owner.to_string());
+                events.push(event);
+            }
         };
         for event in events {
             let _: URef = storage::new_uref(event);
@@ -1,22 +1,29 @@
+use alloc::string::String;
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{bytesrepr::ToBytes, CLTyped, ContractPackageHash, Key, U256};
-use casperlabs_contract_utils::{get_key, set_key};
+use casperlabs_contract_utils::{get_key, null_key, set_key, Dict};
 use core::convert::TryInto;
 
-pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
+// Kept apart from `self_contract_hash`, which `CEP47` uses for a `ContractHash`.
+pub const SELF_CONTRACT_HASH: &str = "pausable_contract_hash";
+// Where `PAUSABLE` kept its hash before the rename, still read by contracts upgraded
+// from that layout.
+pub const LEGACY_SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_PACKAGE_HASH: &str = "self_package_hash";
 pub const LAST_PAUSE_TIME: &str = "last_pause_time";
 pub const PAUSED: &str = "paused";
+pub const PAUSED_UNTIL: &str = "paused_until";
+pub const AUTO_UNPAUSE_AFTER: &str = "auto_unpause_after";
+pub const PAUSED_SCOPES: &str = "paused_scopes";
+pub const SCOPE_PAUSED_UNTIL: &str = "scope_paused_until";
+pub const PAUSERS: &str = "pausers";
 pub const RESULT: &str = "result";
 //Zero Address
 pub fn zero_address() -> Key {
-    Key::from_formatted_str(
-        "hash-0000000000000000000000000000000000000000000000000000000000000000".into(),
-    )
-    .unwrap()
+    null_key()
 }
 pub fn set_result<T: ToBytes + CLTyped>(value: T) {
     match runtime::get_key(RESULT) {
@@ -30,6 +37,112 @@ pub fn set_result<T: ToBytes + CLTyped>(value: T) {
         }
     }
 }
+// Scopes are hashed so dictionary item keys stay within the 64 byte limit.
+fn scope_key(scope: &str) -> String {
+    hex::encode(runtime::blake2b(scope.as_bytes()))
+}
+
+// Contracts upgraded from before scoped pausing never created these dictionaries,
+// so a missing one reads as empty and is created by the first write.
+fn existing_dict(name: &str) -> Option<Dict> {
+    runtime::get_key(name).map(|_| Dict::instance(name))
+}
+fn created_dict(name: &str) -> Dict {
+    if runtime::get_key(name).is_none() {
+        Dict::init(name);
+    }
+    Dict::instance(name)
+}
+
+pub struct PausedScopes {
+    dict: Option<Dict>,
+}
+
+impl PausedScopes {
+    pub fn instance() -> PausedScopes {
+        PausedScopes {
+            dict: existing_dict(PAUSED_SCOPES),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PAUSED_SCOPES)
+    }
+
+    pub fn get(&self, scope: &str) -> bool {
+        self.dict
+            .as_ref()
+            .and_then(|dict| dict.get(&scope_key(scope)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, scope: &str, paused: bool) {
+        match &self.dict {
+            Some(dict) => dict.set(&scope_key(scope), paused),
+            None => created_dict(PAUSED_SCOPES).set(&scope_key(scope), paused),
+        }
+    }
+}
+
+pub struct ScopePausedUntil {
+    dict: Option<Dict>,
+}
+
+impl ScopePausedUntil {
+    pub fn instance() -> ScopePausedUntil {
+        ScopePausedUntil {
+            dict: existing_dict(SCOPE_PAUSED_UNTIL),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SCOPE_PAUSED_UNTIL)
+    }
+
+    pub fn get(&self, scope: &str) -> Option<U256> {
+        self.dict
+            .as_ref()
+            .and_then(|dict| dict.get(&scope_key(scope)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, scope: &str, paused_until: Option<U256>) {
+        match &self.dict {
+            Some(dict) => dict.set(&scope_key(scope), paused_until),
+            None => created_dict(SCOPE_PAUSED_UNTIL).set(&scope_key(scope), paused_until),
+        }
+    }
+}
+
+pub struct Pausers {
+    dict: Option<Dict>,
+}
+
+impl Pausers {
+    pub fn instance() -> Pausers {
+        Pausers {
+            dict: existing_dict(PAUSERS),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PAUSERS)
+    }
+
+    pub fn get(&self, pauser: &Key) -> bool {
+        self.dict
+            .as_ref()
+            .and_then(|dict| dict.get_by_key(pauser))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, pauser: &Key, is_pauser: bool) {
+        match &self.dict {
+            Some(dict) => dict.set_by_key(pauser, is_pauser),
+            None => created_dict(PAUSERS).set_by_key(pauser, is_pauser),
+        }
+    }
+}
 pub fn set_last_pause_time(last_pause_time: U256) {
     set_key(LAST_PAUSE_TIME, last_pause_time);
 }
@@ -42,12 +155,26 @@ pub fn set_paused(paused: bool) {
 pub fn get_paused() -> bool {
     get_key(PAUSED).unwrap_or_default()
 }
+pub fn set_paused_until(paused_until: Option<U256>) {
+    set_key(PAUSED_UNTIL, paused_until);
+}
+pub fn get_paused_until() -> Option<U256> {
+    get_key(PAUSED_UNTIL).unwrap_or_default()
+}
+pub fn set_auto_unpause_after(auto_unpause_after: Option<U256>) {
+    set_key(AUTO_UNPAUSE_AFTER, auto_unpause_after);
+}
+pub fn get_auto_unpause_after() -> Option<U256> {
+    get_key(AUTO_UNPAUSE_AFTER).unwrap_or_default()
+}
 pub fn set_hash(contract_hash: Key) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
 }
 
 pub fn get_hash() -> Key {
-    get_key(SELF_CONTRACT_HASH).unwrap_or_revert()
+    get_key(SELF_CONTRACT_HASH)
+        .or_else(|| get_key(LEGACY_SELF_CONTRACT_HASH))
+        .unwrap_or_revert()
 }
 
 pub fn set_package_hash(package_hash: ContractPackageHash) {
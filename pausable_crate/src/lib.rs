@@ -5,4 +5,4 @@ extern crate alloc;
 pub mod data;
 mod pausable;
 
-pub use pausable::PAUSABLE;
+pub use pausable::{Error, PAUSABLE};
@@ -1,19 +1,25 @@
 use crate::alloc::string::ToString;
-use crate::data;
+use crate::data::{self, PausedScopes, Pausers, ScopePausedUntil};
 use alloc::collections::BTreeMap;
 use alloc::{string::String, vec::Vec};
 use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
 use casper_types::{ApiError, ContractPackageHash, Key, URef, U256};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{is_null_key, ContractContext, ContractStorage};
 use casperlabs_owned::{self, data as owned, OWNED};
+use core::convert::TryFrom;
 //Errors
 #[repr(u16)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     /// Owner must be set
     OwnerMustSet = 20101,
     /// This action cannot be performed while the contract is paused
     ContractPaused = 20102,
+    /// Only the owner or a pauser may pause
+    NotPauser = 20103,
+    /// This action can only be performed while the contract is paused
+    ContractNotPaused = 20104,
 }
 
 impl From<Error> for ApiError {
@@ -21,46 +27,158 @@ impl From<Error> for ApiError {
         ApiError::User(error as u16)
     }
 }
+
+impl TryFrom<u16> for Error {
+    type Error = ();
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            20101 => Ok(Error::OwnerMustSet),
+            20102 => Ok(Error::ContractPaused),
+            20103 => Ok(Error::NotPauser),
+            20104 => Ok(Error::ContractNotPaused),
+            _ => Err(()),
+        }
+    }
+}
 //Events
 pub enum PausableEvent {
     PauseChanged { is_paused: bool },
+    ScopePauseChanged { scope: String, is_paused: bool },
+    PauserChanged { pauser: Key, is_pauser: bool },
 }
 
 impl PausableEvent {
     pub fn type_name(&self) -> String {
         match self {
             PausableEvent::PauseChanged { is_paused: _ } => "PauseChanged",
+            PausableEvent::ScopePauseChanged {
+                scope: _,
+                is_paused: _,
+            } => "ScopePauseChanged",
+            PausableEvent::PauserChanged {
+                pauser: _,
+                is_pauser: _,
+            } => "PauserChanged",
         }
         .to_string()
     }
 }
+fn blocktime() -> U256 {
+    let blocktime: u64 = runtime::get_blocktime().into();
+    U256::from(blocktime)
+}
+
+// Deadline of a pause starting now, fixed at pause time so later changes to
+// `auto_unpause_after` cannot shorten, extend or revive it.
+fn pause_deadline() -> Option<U256> {
+    data::get_auto_unpause_after()
+        .map(|auto_unpause_after| blocktime().saturating_add(auto_unpause_after))
+}
+
+fn lapsed(paused_until: Option<U256>) -> bool {
+    match paused_until {
+        Some(paused_until) => blocktime() >= paused_until,
+        None => false,
+    }
+}
+
 pub trait PAUSABLE<Storage: ContractStorage>: ContractContext<Storage> + OWNED<Storage> {
     fn init(&mut self, contract_hash: Key, package_hash: ContractPackageHash) {
         OWNED::init(self, self.get_caller(), contract_hash, package_hash);
-        if !(owned::get_owner() != data::zero_address()) {
+        if is_null_key(&owned::get_owner()) {
             runtime::revert(ApiError::from(Error::OwnerMustSet));
         }
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
+        PausedScopes::init();
+        ScopePausedUntil::init();
+        Pausers::init();
     }
-    fn set_paused(&mut self, paused: bool) {
+    fn is_pauser(&self, account: Key) -> bool {
+        Pausers::instance().get(&account)
+    }
+    fn set_pauser(&mut self, pauser: Key, is_pauser: bool) {
         OWNED::only_owner(self);
-        if paused == data::get_paused() {
+        Pausers::instance().set(&pauser, is_pauser);
+        self.pausable_emit(&PausableEvent::PauserChanged { pauser, is_pauser });
+    }
+    // Pausers may only pause; unpausing is reserved for the owner.
+    fn only_pause_authority(&self, paused: bool) {
+        let caller = self.get_caller();
+        if !(caller == owned::get_owner() || (paused && Pausers::instance().get(&caller))) {
+            runtime::revert(ApiError::from(Error::NotPauser));
+        }
+    }
+    fn set_paused(&mut self, paused: bool) {
+        self.only_pause_authority(paused);
+        if (paused && self.is_paused()) || (!paused && !data::get_paused()) {
             return;
         }
         data::set_paused(paused);
-        let blocktime: u64 = runtime::get_blocktime().into();
         if paused {
-            data::set_last_pause_time(U256::from(blocktime));
+            data::set_last_pause_time(blocktime());
+            data::set_paused_until(pause_deadline());
         }
         self.pausable_emit(&PausableEvent::PauseChanged { is_paused: paused });
     }
+    // Any scope name works, it is hashed before being used as a dictionary key.
+    fn set_scope_paused(&mut self, scope: String, paused: bool) {
+        self.only_pause_authority(paused);
+        if (paused && self._is_scope_paused(&scope))
+            || (!paused && !PausedScopes::instance().get(&scope))
+        {
+            return;
+        }
+        PausedScopes::instance().set(&scope, paused);
+        if paused {
+            ScopePausedUntil::instance().set(&scope, pause_deadline());
+        }
+        self.pausable_emit(&PausableEvent::ScopePauseChanged {
+            scope,
+            is_paused: paused,
+        });
+    }
+    // A pause lapses on its own once `auto_unpause_after` milliseconds of block time have
+    // passed since it started. Only pauses started after the change are affected.
+    fn set_auto_unpause_after(&mut self, auto_unpause_after: Option<U256>) {
+        OWNED::only_owner(self);
+        data::set_auto_unpause_after(auto_unpause_after);
+    }
+    fn auto_unpause_after(&self) -> Option<U256> {
+        data::get_auto_unpause_after()
+    }
+    fn last_pause_time(&self) -> U256 {
+        data::get_last_pause_time()
+    }
+    fn is_paused(&self) -> bool {
+        data::get_paused() && !lapsed(data::get_paused_until())
+    }
+    fn _is_scope_paused(&self, scope: &str) -> bool {
+        PausedScopes::instance().get(scope) && !lapsed(ScopePausedUntil::instance().get(scope))
+    }
+    // True if `scope` is paused on its own or the whole contract is paused.
+    fn is_scope_paused(&self, scope: &str) -> bool {
+        self.is_paused() || self._is_scope_paused(scope)
+    }
     //Modifier
     fn not_paused(&self) {
-        if !(!data::get_paused()) {
+        if !(!self.is_paused()) {
             runtime::revert(ApiError::from(Error::ContractPaused));
         }
     }
+    //Modifier
+    fn when_not_paused(&self, scope: &str) {
+        if self.is_scope_paused(scope) {
+            runtime::revert(ApiError::from(Error::ContractPaused));
+        }
+    }
+    //Modifier
+    fn when_paused(&self, scope: &str) {
+        if !self.is_scope_paused(scope) {
+            runtime::revert(ApiError::from(Error::ContractNotPaused));
+        }
+    }
     fn pausable_emit(&mut self, pausable_event: &PausableEvent) {
         let mut events = Vec::new();
         let package = data::get_package_hash();
@@ -72,6 +190,22 @@ pub trait PAUSABLE<Storage: ContractStorage>: ContractContext<Storage> + OWNED<S
                 event.insert("is_paused", is_paused.to_string());
                 events.push(event);
             }
+            PausableEvent::ScopePauseChanged { scope, is_paused } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pausable_event.type_name());
+                event.insert("scope", scope.clone());
+                event.insert("is_paused", is_paused.to_string());
+                events.push(event);
+            }
+            PausableEvent::PauserChanged { pauser, is_pauser } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pausable_event.type_name());
+                event.insert("pauser", pauser.to_string());
+                event.insert("is_pauser", is_pauser.to_string());
+                events.push(event);
+            }
         };
         for event in events {
             let _: URef = storage::new_uref(event);
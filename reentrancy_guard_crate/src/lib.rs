@@ -5,4 +5,4 @@ extern crate alloc;
 pub mod data;
 mod reentrancy_guard;
 
-pub use reentrancy_guard::REENTRANCYGUARD;
+pub use reentrancy_guard::{Error, REENTRANCYGUARD};
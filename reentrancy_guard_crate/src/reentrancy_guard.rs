@@ -1,3 +1,5 @@
+use alloc::format;
+use alloc::string::String;
 use casper_contract::contract_api::runtime;
 use casper_types::ApiError;
 use casperlabs_contract_utils::{ContractContext, ContractStorage, set_key,get_key};
@@ -20,6 +22,9 @@ fn set_lock(lock:bool){
 fn get_lock() -> bool{
     get_key(LOCK).unwrap_or_default()
 }
+fn lock_name(name: &str) -> String {
+    format!("{}_{}", LOCK, name)
+}
 pub trait REENTRANCYGUARD<Storage: ContractStorage>: ContractContext<Storage> {
     fn enter(&self) {
         if get_lock()  {
@@ -30,4 +35,33 @@ pub trait REENTRANCYGUARD<Storage: ContractStorage>: ContractContext<Storage> {
     fn leave(&self){
         set_lock(false);
     }
+    fn is_entered(&self) -> bool {
+        get_lock()
+    }
+    /// Runs `f` under the global lock, releasing it on every return path.
+    fn non_reentrant<T, F: FnOnce(&mut Self) -> T>(&mut self, f: F) -> T {
+        self.enter();
+        let ret = f(self);
+        self.leave();
+        ret
+    }
+    // Named locks are independent of each other and of the global lock.
+    fn enter_named(&self, name: &str) {
+        if self.is_entered_named(name) {
+            runtime::revert(Error::ReentrantCall);
+        }
+        set_key(&lock_name(name), true);
+    }
+    fn leave_named(&self, name: &str) {
+        set_key(&lock_name(name), false);
+    }
+    fn is_entered_named(&self, name: &str) -> bool {
+        get_key(&lock_name(name)).unwrap_or_default()
+    }
+    fn non_reentrant_named<T, F: FnOnce(&mut Self) -> T>(&mut self, name: &str, f: F) -> T {
+        self.enter_named(name);
+        let ret = f(self);
+        self.leave_named(name);
+        ret
+    }
 }
@@ -0,0 +1,187 @@
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{Key, U256};
+use casperlabs_contract_utils::{get_key, set_key, Dict};
+use casperlabs_lp_token_wrapper::TokenInterface;
+use core::convert::TryFrom;
+
+pub const REWARDS_TOKEN: &str = "rewards_token";
+pub const REWARDS_TOKEN_INTERFACE: &str = "rewards_token_interface";
+pub const DURATION: &str = "duration";
+pub const PERIOD_FINISH: &str = "period_finish";
+pub const REWARD_RATE: &str = "reward_rate";
+pub const LAST_UPDATE_TIME: &str = "last_update_time";
+pub const REWARD_PER_TOKEN_STORED: &str = "reward_per_token_stored";
+pub const USER_REWARD_PER_TOKEN_PAID_DICT: &str = "user_reward_per_token_paid";
+pub const REWARDS_DICT: &str = "rewards";
+
+pub const REWARDS_TOKEN_A: &str = "rewards_token_a";
+pub const REWARDS_TOKEN_B: &str = "rewards_token_b";
+pub const REWARDS_TOKEN_A_INTERFACE: &str = "rewards_token_a_interface";
+pub const REWARDS_TOKEN_B_INTERFACE: &str = "rewards_token_b_interface";
+pub const REWARD_RATE_A: &str = "reward_rate_a";
+pub const REWARD_RATE_B: &str = "reward_rate_b";
+pub const REWARD_PER_TOKEN_A_STORED: &str = "reward_per_token_a_stored";
+pub const REWARD_PER_TOKEN_B_STORED: &str = "reward_per_token_b_stored";
+pub const USER_REWARD_PER_TOKEN_A_PAID_DICT: &str = "user_reward_per_token_a_paid";
+pub const USER_REWARD_PER_TOKEN_B_PAID_DICT: &str = "user_reward_per_token_b_paid";
+pub const REWARDS_A_DICT: &str = "rewards_a";
+pub const REWARDS_B_DICT: &str = "rewards_b";
+
+pub struct UserRewardPerTokenPaid {
+    dict: Dict,
+}
+
+impl UserRewardPerTokenPaid {
+    pub fn instance() -> UserRewardPerTokenPaid {
+        UserRewardPerTokenPaid::named(USER_REWARD_PER_TOKEN_PAID_DICT)
+    }
+
+    pub fn named(name: &str) -> UserRewardPerTokenPaid {
+        UserRewardPerTokenPaid {
+            dict: Dict::instance(name),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(USER_REWARD_PER_TOKEN_PAID_DICT)
+    }
+
+    pub fn init_named(name: &str) {
+        Dict::init(name)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get_by_key(owner).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set_by_key(owner, value)
+    }
+}
+
+pub struct Rewards {
+    dict: Dict,
+}
+
+impl Rewards {
+    pub fn instance() -> Rewards {
+        Rewards::named(REWARDS_DICT)
+    }
+
+    pub fn named(name: &str) -> Rewards {
+        Rewards {
+            dict: Dict::instance(name),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(REWARDS_DICT)
+    }
+
+    pub fn init_named(name: &str) {
+        Dict::init(name)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get_by_key(owner).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set_by_key(owner, value)
+    }
+}
+
+pub fn set_rewards_token(rewards_token: Key) {
+    set_key(REWARDS_TOKEN, rewards_token);
+}
+pub fn get_rewards_token() -> Key {
+    get_key(REWARDS_TOKEN).unwrap_or_else(casperlabs_lp_token_wrapper::data::zero_address)
+}
+pub fn set_rewards_token_interface(interface: TokenInterface) {
+    set_key(REWARDS_TOKEN_INTERFACE, interface as u8);
+}
+pub fn get_rewards_token_interface() -> TokenInterface {
+    TokenInterface::try_from(get_key::<u8>(REWARDS_TOKEN_INTERFACE).unwrap_or_default())
+        .unwrap_or_revert()
+}
+pub fn set_duration(duration: U256) {
+    set_key(DURATION, duration);
+}
+pub fn get_duration() -> U256 {
+    get_key(DURATION).unwrap_or_default()
+}
+pub fn set_period_finish(period_finish: U256) {
+    set_key(PERIOD_FINISH, period_finish);
+}
+pub fn get_period_finish() -> U256 {
+    get_key(PERIOD_FINISH).unwrap_or_default()
+}
+pub fn set_reward_rate(reward_rate: U256) {
+    set_key(REWARD_RATE, reward_rate);
+}
+pub fn get_reward_rate() -> U256 {
+    get_key(REWARD_RATE).unwrap_or_default()
+}
+pub fn set_last_update_time(last_update_time: U256) {
+    set_key(LAST_UPDATE_TIME, last_update_time);
+}
+pub fn get_last_update_time() -> U256 {
+    get_key(LAST_UPDATE_TIME).unwrap_or_default()
+}
+pub fn set_reward_per_token_stored(reward_per_token_stored: U256) {
+    set_key(REWARD_PER_TOKEN_STORED, reward_per_token_stored);
+}
+pub fn get_reward_per_token_stored() -> U256 {
+    get_key(REWARD_PER_TOKEN_STORED).unwrap_or_default()
+}
+
+pub fn set_rewards_token_a(rewards_token_a: Key) {
+    set_key(REWARDS_TOKEN_A, rewards_token_a);
+}
+pub fn get_rewards_token_a() -> Key {
+    get_key(REWARDS_TOKEN_A).unwrap_or_else(casperlabs_lp_token_wrapper::data::zero_address)
+}
+pub fn set_rewards_token_b(rewards_token_b: Key) {
+    set_key(REWARDS_TOKEN_B, rewards_token_b);
+}
+pub fn get_rewards_token_b() -> Key {
+    get_key(REWARDS_TOKEN_B).unwrap_or_else(casperlabs_lp_token_wrapper::data::zero_address)
+}
+pub fn set_rewards_token_a_interface(interface: TokenInterface) {
+    set_key(REWARDS_TOKEN_A_INTERFACE, interface as u8);
+}
+pub fn get_rewards_token_a_interface() -> TokenInterface {
+    TokenInterface::try_from(get_key::<u8>(REWARDS_TOKEN_A_INTERFACE).unwrap_or_default())
+        .unwrap_or_revert()
+}
+pub fn set_rewards_token_b_interface(interface: TokenInterface) {
+    set_key(REWARDS_TOKEN_B_INTERFACE, interface as u8);
+}
+pub fn get_rewards_token_b_interface() -> TokenInterface {
+    TokenInterface::try_from(get_key::<u8>(REWARDS_TOKEN_B_INTERFACE).unwrap_or_default())
+        .unwrap_or_revert()
+}
+pub fn set_reward_rate_a(reward_rate_a: U256) {
+    set_key(REWARD_RATE_A, reward_rate_a);
+}
+pub fn get_reward_rate_a() -> U256 {
+    get_key(REWARD_RATE_A).unwrap_or_default()
+}
+pub fn set_reward_rate_b(reward_rate_b: U256) {
+    set_key(REWARD_RATE_B, reward_rate_b);
+}
+pub fn get_reward_rate_b() -> U256 {
+    get_key(REWARD_RATE_B).unwrap_or_default()
+}
+pub fn set_reward_per_token_a_stored(reward_per_token_a_stored: U256) {
+    set_key(REWARD_PER_TOKEN_A_STORED, reward_per_token_a_stored);
+}
+pub fn get_reward_per_token_a_stored() -> U256 {
+    get_key(REWARD_PER_TOKEN_A_STORED).unwrap_or_default()
+}
+pub fn set_reward_per_token_b_stored(reward_per_token_b_stored: U256) {
+    set_key(REWARD_PER_TOKEN_B_STORED, reward_per_token_b_stored);
+}
+pub fn get_reward_per_token_b_stored() -> U256 {
+    get_key(REWARD_PER_TOKEN_B_STORED).unwrap_or_default()
+}
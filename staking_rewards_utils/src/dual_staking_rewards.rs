@@ -0,0 +1,449 @@
+use crate::data::{self, Rewards, UserRewardPerTokenPaid};
+use crate::staking_rewards::{blocktime, precision, reward_balance};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, ContractHash, ContractPackageHash, Key, URef, U256};
+use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_lp_token_wrapper::{
+    data as lp_token_wrapper, safe_transfer, TokenInterface, LPTOKENWRAPPER,
+};
+use casperlabs_owned::OWNED;
+use dual_rewards_distribution_recipient_crate::DUALREWARDSDISTRIBUTIONRECIPIENT;
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,540 for (DualStakingRewards: Cannot stake 0)
+    CannotStakeZero = 12101,
+    /// 65,540 for (DualStakingRewards: Cannot withdraw 0)
+    CannotWithdrawZero = 12102,
+    /// 65,540 for (DualStakingRewards: Reward transfer failed)
+    RewardTransferFailed = 12103,
+    /// 65,540 for (DualStakingRewards: Duration must be greater than 0)
+    DurationZero = 12104,
+    /// 65,540 for (DualStakingRewards: Previous rewards period must be complete)
+    RewardPeriodNotComplete = 12105,
+    /// 65,540 for (DualStakingRewards: Overflow)
+    DualStakingRewardsOverflow = 12106,
+    /// 65,540 for (DualStakingRewards: Underflow)
+    DualStakingRewardsUnderflow = 12107,
+    /// 65,540 for (DualStakingRewards: Provided reward too high)
+    ProvidedRewardTooHigh = 12108,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+pub enum DualStakingRewardsEvent {
+    RewardAdded { reward_a: U256, reward_b: U256 },
+    RewardPaid { user: Key, token: Key, reward: U256 },
+    RewardsDurationUpdated { duration: U256 },
+}
+
+impl DualStakingRewardsEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            DualStakingRewardsEvent::RewardAdded { .. } => "RewardAdded",
+            DualStakingRewardsEvent::RewardPaid { .. } => "RewardPaid",
+            DualStakingRewardsEvent::RewardsDurationUpdated { .. } => "RewardsDurationUpdated",
+        }
+        .to_string()
+    }
+}
+
+fn accrue(stored: U256, elapsed: U256, reward_rate: U256, total_supply: U256) -> U256 {
+    let accrued = elapsed
+        .checked_mul(reward_rate)
+        .and_then(|value| value.checked_mul(precision()))
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow)
+        / total_supply;
+    stored
+        .checked_add(accrued)
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow)
+}
+
+fn earned(balance: U256, reward_per_token: U256, paid: U256, rewards: U256) -> U256 {
+    let pending = reward_per_token
+        .checked_sub(paid)
+        .unwrap_or_revert_with(Error::DualStakingRewardsUnderflow);
+    let earned = balance
+        .checked_mul(pending)
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow)
+        / precision();
+    earned
+        .checked_add(rewards)
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow)
+}
+
+fn next_reward_rate(
+    reward: U256,
+    reward_rate: U256,
+    now: U256,
+    period_finish: U256,
+    duration: U256,
+) -> U256 {
+    if now >= period_finish {
+        return reward / duration;
+    }
+    let leftover = (period_finish - now)
+        .checked_mul(reward_rate)
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow);
+    reward
+        .checked_add(leftover)
+        .unwrap_or_revert_with(Error::DualStakingRewardsOverflow)
+        / duration
+}
+
+pub trait DUALSTAKINGREWARDS<Storage: ContractStorage>:
+    ContractContext<Storage> + LPTOKENWRAPPER<Storage> + DUALREWARDSDISTRIBUTIONRECIPIENT<Storage>
+{
+    #[allow(clippy::too_many_arguments)]
+    fn init(
+        &mut self,
+        owner: Key,
+        dual_rewards_distribution: Key,
+        rewards_token_a: Key,
+        rewards_token_b: Key,
+        staking_token: Key,
+        duration: U256,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        self.init_with_token_interfaces(
+            owner,
+            dual_rewards_distribution,
+            rewards_token_a,
+            TokenInterface::Key,
+            rewards_token_b,
+            TokenInterface::Key,
+            staking_token,
+            TokenInterface::Key,
+            duration,
+            contract_hash,
+            package_hash,
+        );
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn init_with_token_interfaces(
+        &mut self,
+        owner: Key,
+        dual_rewards_distribution: Key,
+        rewards_token_a: Key,
+        rewards_token_a_interface: TokenInterface,
+        rewards_token_b: Key,
+        rewards_token_b_interface: TokenInterface,
+        staking_token: Key,
+        staking_token_interface: TokenInterface,
+        duration: U256,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        if duration.is_zero() {
+            runtime::revert(ApiError::from(Error::DurationZero));
+        }
+        LPTOKENWRAPPER::init_with_token_interface(
+            self,
+            staking_token,
+            staking_token_interface,
+            contract_hash,
+            package_hash,
+        );
+        DUALREWARDSDISTRIBUTIONRECIPIENT::init(
+            self,
+            owner,
+            dual_rewards_distribution,
+            Key::from(contract_hash),
+            package_hash,
+        );
+        data::set_rewards_token_a(rewards_token_a);
+        data::set_rewards_token_b(rewards_token_b);
+        data::set_rewards_token_a_interface(rewards_token_a_interface);
+        data::set_rewards_token_b_interface(rewards_token_b_interface);
+        data::set_duration(duration);
+        UserRewardPerTokenPaid::init_named(data::USER_REWARD_PER_TOKEN_A_PAID_DICT);
+        UserRewardPerTokenPaid::init_named(data::USER_REWARD_PER_TOKEN_B_PAID_DICT);
+        Rewards::init_named(data::REWARDS_A_DICT);
+        Rewards::init_named(data::REWARDS_B_DICT);
+    }
+    fn rewards_token_a(&self) -> Key {
+        data::get_rewards_token_a()
+    }
+    fn rewards_token_b(&self) -> Key {
+        data::get_rewards_token_b()
+    }
+    fn duration(&self) -> U256 {
+        data::get_duration()
+    }
+    fn period_finish(&self) -> U256 {
+        data::get_period_finish()
+    }
+    fn reward_rate_a(&self) -> U256 {
+        data::get_reward_rate_a()
+    }
+    fn reward_rate_b(&self) -> U256 {
+        data::get_reward_rate_b()
+    }
+    fn last_update_time(&self) -> U256 {
+        data::get_last_update_time()
+    }
+    fn user_reward_per_token_a_paid(&self, account: Key) -> U256 {
+        UserRewardPerTokenPaid::named(data::USER_REWARD_PER_TOKEN_A_PAID_DICT).get(&account)
+    }
+    fn user_reward_per_token_b_paid(&self, account: Key) -> U256 {
+        UserRewardPerTokenPaid::named(data::USER_REWARD_PER_TOKEN_B_PAID_DICT).get(&account)
+    }
+    fn rewards_a(&self, account: Key) -> U256 {
+        Rewards::named(data::REWARDS_A_DICT).get(&account)
+    }
+    fn rewards_b(&self, account: Key) -> U256 {
+        Rewards::named(data::REWARDS_B_DICT).get(&account)
+    }
+    fn last_time_reward_applicable(&self) -> U256 {
+        blocktime().min(data::get_period_finish())
+    }
+    fn reward_per_token_a(&self) -> U256 {
+        let total_supply = LPTOKENWRAPPER::weighted_total_supply(self);
+        if total_supply.is_zero() {
+            return data::get_reward_per_token_a_stored();
+        }
+        let elapsed = self
+            .last_time_reward_applicable()
+            .checked_sub(data::get_last_update_time())
+            .unwrap_or_revert_with(Error::DualStakingRewardsUnderflow);
+        accrue(
+            data::get_reward_per_token_a_stored(),
+            elapsed,
+            data::get_reward_rate_a(),
+            total_supply,
+        )
+    }
+    fn reward_per_token_b(&self) -> U256 {
+        let total_supply = LPTOKENWRAPPER::weighted_total_supply(self);
+        if total_supply.is_zero() {
+            return data::get_reward_per_token_b_stored();
+        }
+        let elapsed = self
+            .last_time_reward_applicable()
+            .checked_sub(data::get_last_update_time())
+            .unwrap_or_revert_with(Error::DualStakingRewardsUnderflow);
+        accrue(
+            data::get_reward_per_token_b_stored(),
+            elapsed,
+            data::get_reward_rate_b(),
+            total_supply,
+        )
+    }
+    fn earned_a(&self, account: Key) -> U256 {
+        earned(
+            LPTOKENWRAPPER::weighted_balance_of(self, account),
+            self.reward_per_token_a(),
+            self.user_reward_per_token_a_paid(account),
+            self.rewards_a(account),
+        )
+    }
+    fn earned_b(&self, account: Key) -> U256 {
+        earned(
+            LPTOKENWRAPPER::weighted_balance_of(self, account),
+            self.reward_per_token_b(),
+            self.user_reward_per_token_b_paid(account),
+            self.rewards_b(account),
+        )
+    }
+    // Modifier
+    fn update_reward(&mut self, account: Option<Key>) {
+        data::set_reward_per_token_a_stored(self.reward_per_token_a());
+        data::set_reward_per_token_b_stored(self.reward_per_token_b());
+        data::set_last_update_time(self.last_time_reward_applicable());
+        if let Some(account) = account {
+            Rewards::named(data::REWARDS_A_DICT).set(&account, self.earned_a(account));
+            Rewards::named(data::REWARDS_B_DICT).set(&account, self.earned_b(account));
+            UserRewardPerTokenPaid::named(data::USER_REWARD_PER_TOKEN_A_PAID_DICT)
+                .set(&account, data::get_reward_per_token_a_stored());
+            UserRewardPerTokenPaid::named(data::USER_REWARD_PER_TOKEN_B_PAID_DICT)
+                .set(&account, data::get_reward_per_token_b_stored());
+        }
+    }
+    fn stake(&mut self, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::stake(self, amount);
+    }
+    fn stake_for(&mut self, beneficiary: Key, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(beneficiary));
+        LPTOKENWRAPPER::stake_for(self, beneficiary, amount);
+    }
+    fn withdraw(&mut self, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotWithdrawZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::withdraw(self, amount);
+    }
+    fn withdraw_for(&mut self, owner: Key, recipient: Key, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotWithdrawZero));
+        }
+        self.update_reward(Some(owner));
+        LPTOKENWRAPPER::withdraw_for(self, owner, recipient, amount);
+    }
+    fn stake_locked(&mut self, amount: U256, lock_duration: u64) -> U256 {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::stake_locked(self, amount, lock_duration)
+    }
+    fn set_lock_schedule(&mut self, lock_schedule: Vec<(u64, U256)>) {
+        OWNED::only_owner(self);
+        LPTOKENWRAPPER::_set_lock_schedule(self, lock_schedule);
+    }
+    fn set_penalty_schedule(&mut self, penalty_schedule: Vec<(u64, U256)>) {
+        OWNED::only_owner(self);
+        LPTOKENWRAPPER::_set_penalty_schedule(self, penalty_schedule);
+    }
+    fn set_penalty_recipient(&mut self, penalty_recipient: Key) {
+        OWNED::only_owner(self);
+        LPTOKENWRAPPER::_set_penalty_recipient(self, penalty_recipient);
+    }
+    fn withdraw_position(&mut self, id: U256) {
+        self.update_reward(Some(LPTOKENWRAPPER::position(self, id).owner));
+        LPTOKENWRAPPER::withdraw_position(self, id);
+    }
+    // Only the flexible balance is withdrawn, locked positions are closed one by one
+    // through `withdraw_position`.
+    fn exit(&mut self) {
+        DUALSTAKINGREWARDS::withdraw(self, LPTOKENWRAPPER::balance_of(self, self.get_caller()));
+        self.get_reward();
+    }
+    fn get_reward(&mut self) {
+        let caller = self.get_caller();
+        self.update_reward(Some(caller));
+        let reward_a = self.rewards_a(caller);
+        if !reward_a.is_zero() {
+            Rewards::named(data::REWARDS_A_DICT).set(&caller, U256::zero());
+            let token = data::get_rewards_token_a();
+            safe_transfer(
+                token,
+                data::get_rewards_token_a_interface(),
+                caller,
+                reward_a,
+            );
+            self.dual_staking_rewards_emit(&DualStakingRewardsEvent::RewardPaid {
+                user: caller,
+                token,
+                reward: reward_a,
+            });
+        }
+        let reward_b = self.rewards_b(caller);
+        if !reward_b.is_zero() {
+            Rewards::named(data::REWARDS_B_DICT).set(&caller, U256::zero());
+            let token = data::get_rewards_token_b();
+            safe_transfer(
+                token,
+                data::get_rewards_token_b_interface(),
+                caller,
+                reward_b,
+            );
+            self.dual_staking_rewards_emit(&DualStakingRewardsEvent::RewardPaid {
+                user: caller,
+                token,
+                reward: reward_b,
+            });
+        }
+    }
+    fn notify_reward_amount(&mut self, reward_a: U256, reward_b: U256) {
+        DUALREWARDSDISTRIBUTIONRECIPIENT::only_dual_rewards_distribution(self);
+        self.update_reward(None);
+        let now = blocktime();
+        let period_finish = data::get_period_finish();
+        let duration = data::get_duration();
+        let reward_rate_a = next_reward_rate(
+            reward_a,
+            data::get_reward_rate_a(),
+            now,
+            period_finish,
+            duration,
+        );
+        let reward_rate_b = next_reward_rate(
+            reward_b,
+            data::get_reward_rate_b(),
+            now,
+            period_finish,
+            duration,
+        );
+        // Ensures both rates can be paid out of the balances the contract already holds.
+        let balance_a = reward_balance(
+            data::get_rewards_token_a(),
+            data::get_rewards_token_a_interface(),
+        );
+        let balance_b = reward_balance(
+            data::get_rewards_token_b(),
+            data::get_rewards_token_b_interface(),
+        );
+        if reward_rate_a > balance_a / duration || reward_rate_b > balance_b / duration {
+            runtime::revert(ApiError::from(Error::ProvidedRewardTooHigh));
+        }
+        data::set_reward_rate_a(reward_rate_a);
+        data::set_reward_rate_b(reward_rate_b);
+        data::set_last_update_time(now);
+        data::set_period_finish(
+            now.checked_add(duration)
+                .unwrap_or_revert_with(Error::DualStakingRewardsOverflow),
+        );
+        self.dual_staking_rewards_emit(&DualStakingRewardsEvent::RewardAdded {
+            reward_a,
+            reward_b,
+        });
+    }
+    fn set_rewards_duration(&mut self, duration: U256) {
+        OWNED::only_owner(self);
+        if blocktime() <= data::get_period_finish() {
+            runtime::revert(ApiError::from(Error::RewardPeriodNotComplete));
+        }
+        if duration.is_zero() {
+            runtime::revert(ApiError::from(Error::DurationZero));
+        }
+        data::set_duration(duration);
+        self.dual_staking_rewards_emit(&DualStakingRewardsEvent::RewardsDurationUpdated {
+            duration,
+        });
+    }
+
+    fn dual_staking_rewards_emit(&mut self, dual_staking_rewards_event: &DualStakingRewardsEvent) {
+        let package = lp_token_wrapper::get_package_hash();
+        let mut event = BTreeMap::new();
+        event.insert("contract_package_hash", package.to_string());
+        event.insert("event_type", dual_staking_rewards_event.type_name());
+        match dual_staking_rewards_event {
+            DualStakingRewardsEvent::RewardAdded { reward_a, reward_b } => {
+                event.insert("reward_a", reward_a.to_string());
+                event.insert("reward_b", reward_b.to_string());
+            }
+            DualStakingRewardsEvent::RewardPaid {
+                user,
+                token,
+                reward,
+            } => {
+                event.insert("user", user.to_string());
+                event.insert("token", token.to_string());
+                event.insert("reward", reward.to_string());
+            }
+            DualStakingRewardsEvent::RewardsDurationUpdated { duration } => {
+                event.insert("duration", duration.to_string());
+            }
+        };
+        let _: URef = storage::new_uref(event);
+    }
+}
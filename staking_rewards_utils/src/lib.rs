@@ -1,3 +1,11 @@
 #![no_std]
 
+extern crate alloc;
+
 pub mod commons;
+pub mod data;
+mod dual_staking_rewards;
+mod staking_rewards;
+
+pub use dual_staking_rewards::{Error as DualStakingRewardsError, DUALSTAKINGREWARDS};
+pub use staking_rewards::{Error, STAKINGREWARDS};
@@ -0,0 +1,320 @@
+use crate::data::{self, Rewards, UserRewardPerTokenPaid};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, ContractHash, ContractPackageHash, Key, URef, U256};
+use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_i_reward_distribution_recipient::IREWARDDISTRIBUTIONRECIPIENT;
+use casperlabs_lp_token_wrapper::{
+    balance_of, data as lp_token_wrapper, safe_transfer, TokenInterface, LPTOKENWRAPPER,
+};
+use casperlabs_ownable::OWNABLE;
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,540 for (StakingRewards: Cannot stake 0)
+    CannotStakeZero = 12001,
+    /// 65,540 for (StakingRewards: Cannot withdraw 0)
+    CannotWithdrawZero = 12002,
+    /// 65,540 for (StakingRewards: Reward transfer failed)
+    RewardTransferFailed = 12003,
+    /// 65,540 for (StakingRewards: Duration must be greater than 0)
+    DurationZero = 12004,
+    /// 65,540 for (StakingRewards: Overflow)
+    StakingRewardsOverflow = 12005,
+    /// 65,540 for (StakingRewards: Underflow)
+    StakingRewardsUnderflow = 12006,
+    /// 65,540 for (StakingRewards: Provided reward too high)
+    ProvidedRewardTooHigh = 12007,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+pub enum StakingRewardsEvent {
+    RewardAdded { reward: U256 },
+    RewardPaid { user: Key, reward: U256 },
+}
+
+impl StakingRewardsEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            StakingRewardsEvent::RewardAdded { reward: _ } => "RewardAdded",
+            StakingRewardsEvent::RewardPaid { user: _, reward: _ } => "RewardPaid",
+        }
+        .to_string()
+    }
+}
+
+pub(crate) fn precision() -> U256 {
+    U256::from(10).pow(18.into())
+}
+
+pub(crate) fn blocktime() -> U256 {
+    let blocktime: u64 = runtime::get_blocktime().into();
+    U256::from(blocktime)
+}
+
+// Balance of `token` this contract can pay rewards from, staked principal excluded.
+pub(crate) fn reward_balance(token: Key, interface: TokenInterface) -> U256 {
+    let balance = balance_of(
+        token,
+        interface,
+        Key::from(lp_token_wrapper::get_package_hash()),
+    );
+    if token == lp_token_wrapper::get_uni() {
+        return balance.saturating_sub(lp_token_wrapper::get_total_supply());
+    }
+    balance
+}
+
+pub trait STAKINGREWARDS<Storage: ContractStorage>:
+    ContractContext<Storage> + LPTOKENWRAPPER<Storage> + IREWARDDISTRIBUTIONRECIPIENT<Storage>
+{
+    fn init(
+        &mut self,
+        rewards_token: Key,
+        staking_token: Key,
+        duration: U256,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        self.init_with_token_interfaces(
+            rewards_token,
+            TokenInterface::Key,
+            staking_token,
+            TokenInterface::Key,
+            duration,
+            contract_hash,
+            package_hash,
+        );
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn init_with_token_interfaces(
+        &mut self,
+        rewards_token: Key,
+        rewards_token_interface: TokenInterface,
+        staking_token: Key,
+        staking_token_interface: TokenInterface,
+        duration: U256,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        LPTOKENWRAPPER::init_with_token_interface(
+            self,
+            staking_token,
+            staking_token_interface,
+            contract_hash,
+            package_hash,
+        );
+        IREWARDDISTRIBUTIONRECIPIENT::init(self, contract_hash, package_hash);
+        data::set_rewards_token(rewards_token);
+        data::set_rewards_token_interface(rewards_token_interface);
+        data::set_duration(duration);
+        UserRewardPerTokenPaid::init();
+        Rewards::init();
+    }
+    fn rewards_token(&self) -> Key {
+        data::get_rewards_token()
+    }
+    fn duration(&self) -> U256 {
+        data::get_duration()
+    }
+    fn period_finish(&self) -> U256 {
+        data::get_period_finish()
+    }
+    fn reward_rate(&self) -> U256 {
+        data::get_reward_rate()
+    }
+    fn last_update_time(&self) -> U256 {
+        data::get_last_update_time()
+    }
+    fn reward_per_token_stored(&self) -> U256 {
+        data::get_reward_per_token_stored()
+    }
+    fn user_reward_per_token_paid(&self, account: Key) -> U256 {
+        UserRewardPerTokenPaid::instance().get(&account)
+    }
+    fn rewards(&self, account: Key) -> U256 {
+        Rewards::instance().get(&account)
+    }
+    fn last_time_reward_applicable(&self) -> U256 {
+        blocktime().min(data::get_period_finish())
+    }
+    fn reward_per_token(&self) -> U256 {
+        let total_supply = LPTOKENWRAPPER::weighted_total_supply(self);
+        if total_supply.is_zero() {
+            return data::get_reward_per_token_stored();
+        }
+        let elapsed = self
+            .last_time_reward_applicable()
+            .checked_sub(data::get_last_update_time())
+            .unwrap_or_revert_with(Error::StakingRewardsUnderflow);
+        let accrued = elapsed
+            .checked_mul(data::get_reward_rate())
+            .and_then(|value| value.checked_mul(precision()))
+            .unwrap_or_revert_with(Error::StakingRewardsOverflow)
+            / total_supply;
+        data::get_reward_per_token_stored()
+            .checked_add(accrued)
+            .unwrap_or_revert_with(Error::StakingRewardsOverflow)
+    }
+    fn earned(&self, account: Key) -> U256 {
+        let pending = self
+            .reward_per_token()
+            .checked_sub(UserRewardPerTokenPaid::instance().get(&account))
+            .unwrap_or_revert_with(Error::StakingRewardsUnderflow);
+        let earned = LPTOKENWRAPPER::weighted_balance_of(self, account)
+            .checked_mul(pending)
+            .unwrap_or_revert_with(Error::StakingRewardsOverflow)
+            / precision();
+        earned
+            .checked_add(Rewards::instance().get(&account))
+            .unwrap_or_revert_with(Error::StakingRewardsOverflow)
+    }
+    // Modifier
+    fn update_reward(&mut self, account: Option<Key>) {
+        data::set_reward_per_token_stored(self.reward_per_token());
+        data::set_last_update_time(self.last_time_reward_applicable());
+        if let Some(account) = account {
+            Rewards::instance().set(&account, self.earned(account));
+            UserRewardPerTokenPaid::instance().set(&account, data::get_reward_per_token_stored());
+        }
+    }
+    fn stake(&mut self, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::stake(self, amount);
+    }
+    fn stake_for(&mut self, beneficiary: Key, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(beneficiary));
+        LPTOKENWRAPPER::stake_for(self, beneficiary, amount);
+    }
+    fn withdraw(&mut self, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotWithdrawZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::withdraw(self, amount);
+    }
+    fn withdraw_for(&mut self, owner: Key, recipient: Key, amount: U256) {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotWithdrawZero));
+        }
+        self.update_reward(Some(owner));
+        LPTOKENWRAPPER::withdraw_for(self, owner, recipient, amount);
+    }
+    fn stake_locked(&mut self, amount: U256, lock_duration: u64) -> U256 {
+        if amount.is_zero() {
+            runtime::revert(ApiError::from(Error::CannotStakeZero));
+        }
+        self.update_reward(Some(self.get_caller()));
+        LPTOKENWRAPPER::stake_locked(self, amount, lock_duration)
+    }
+    fn set_lock_schedule(&mut self, lock_schedule: Vec<(u64, U256)>) {
+        OWNABLE::only_owner(self);
+        LPTOKENWRAPPER::_set_lock_schedule(self, lock_schedule);
+    }
+    fn set_penalty_schedule(&mut self, penalty_schedule: Vec<(u64, U256)>) {
+        OWNABLE::only_owner(self);
+        LPTOKENWRAPPER::_set_penalty_schedule(self, penalty_schedule);
+    }
+    fn set_penalty_recipient(&mut self, penalty_recipient: Key) {
+        OWNABLE::only_owner(self);
+        LPTOKENWRAPPER::_set_penalty_recipient(self, penalty_recipient);
+    }
+    fn withdraw_position(&mut self, id: U256) {
+        self.update_reward(Some(LPTOKENWRAPPER::position(self, id).owner));
+        LPTOKENWRAPPER::withdraw_position(self, id);
+    }
+    // Only the flexible balance is withdrawn, locked positions are closed one by one
+    // through `withdraw_position`.
+    fn exit(&mut self) {
+        STAKINGREWARDS::withdraw(self, LPTOKENWRAPPER::balance_of(self, self.get_caller()));
+        self.get_reward();
+    }
+    fn get_reward(&mut self) {
+        let caller = self.get_caller();
+        self.update_reward(Some(caller));
+        let reward = Rewards::instance().get(&caller);
+        if reward.is_zero() {
+            return;
+        }
+        Rewards::instance().set(&caller, U256::zero());
+        safe_transfer(
+            data::get_rewards_token(),
+            data::get_rewards_token_interface(),
+            caller,
+            reward,
+        );
+        self.staking_rewards_emit(&StakingRewardsEvent::RewardPaid {
+            user: caller,
+            reward,
+        });
+    }
+    fn notify_reward_amount(&mut self, reward: U256, duration: U256) {
+        IREWARDDISTRIBUTIONRECIPIENT::only_reward_distribution(self);
+        if duration.is_zero() {
+            runtime::revert(ApiError::from(Error::DurationZero));
+        }
+        self.update_reward(None);
+        let now = blocktime();
+        let period_finish = data::get_period_finish();
+        let reward_rate = if now >= period_finish {
+            reward / duration
+        } else {
+            let leftover = (period_finish - now)
+                .checked_mul(data::get_reward_rate())
+                .unwrap_or_revert_with(Error::StakingRewardsOverflow);
+            reward
+                .checked_add(leftover)
+                .unwrap_or_revert_with(Error::StakingRewardsOverflow)
+                / duration
+        };
+        // Ensures the rate can be paid out of the balance the contract already holds.
+        let balance = reward_balance(
+            data::get_rewards_token(),
+            data::get_rewards_token_interface(),
+        );
+        if reward_rate > balance / duration {
+            runtime::revert(ApiError::from(Error::ProvidedRewardTooHigh));
+        }
+        data::set_reward_rate(reward_rate);
+        data::set_duration(duration);
+        data::set_last_update_time(now);
+        data::set_period_finish(
+            now.checked_add(duration)
+                .unwrap_or_revert_with(Error::StakingRewardsOverflow),
+        );
+        self.staking_rewards_emit(&StakingRewardsEvent::RewardAdded { reward });
+    }
+
+    fn staking_rewards_emit(&mut self, staking_rewards_event: &StakingRewardsEvent) {
+        let package = lp_token_wrapper::get_package_hash();
+        let mut event = BTreeMap::new();
+        event.insert("contract_package_hash", package.to_string());
+        event.insert("event_type", staking_rewards_event.type_name());
+        match staking_rewards_event {
+            StakingRewardsEvent::RewardAdded { reward } => {
+                event.insert("reward", reward.to_string());
+            }
+            StakingRewardsEvent::RewardPaid { user, reward } => {
+                event.insert("user", user.to_string());
+                event.insert("reward", reward.to_string());
+            }
+        };
+        let _: URef = storage::new_uref(event);
+    }
+}
@@ -0,0 +1,87 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error,
+    bytesrepr::{self, Bytes, ToBytes},
+    ContractVersion, Key, URef, UREF_SERIALIZED_LENGTH,
+};
+
+const RESULT: &str = "result";
+
+// Calls `entrypoint` on the stored contract package and returns the serialized `CLValue`
+// without checking its type, so one proxy works for every return type.
+fn call_versioned_contract_raw(package_hash: Key, entrypoint: String, args: Bytes) -> Vec<u8> {
+    let package_hash_bytes = package_hash
+        .into_hash()
+        .unwrap_or_revert()
+        .to_bytes()
+        .unwrap_or_revert();
+    let version_bytes = Option::<ContractVersion>::None
+        .to_bytes()
+        .unwrap_or_revert();
+    let entrypoint_bytes = entrypoint.to_bytes().unwrap_or_revert();
+    let mut result_size = 0usize;
+    let ret = unsafe {
+        ext_ffi::casper_call_versioned_contract(
+            package_hash_bytes.as_ptr(),
+            package_hash_bytes.len(),
+            version_bytes.as_ptr(),
+            version_bytes.len(),
+            entrypoint_bytes.as_ptr(),
+            entrypoint_bytes.len(),
+            args.as_ptr(),
+            args.len(),
+            &mut result_size as *mut usize,
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    if result_size == 0 {
+        return Vec::new();
+    }
+    let mut result = vec![0u8; result_size];
+    let mut bytes_written = 0usize;
+    let ret = unsafe {
+        ext_ffi::casper_read_host_buffer(
+            result.as_mut_ptr(),
+            result_size,
+            &mut bytes_written as *mut usize,
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    result
+}
+
+fn new_uref_raw(cl_value: &[u8]) -> URef {
+    let mut uref_bytes = vec![0u8; UREF_SERIALIZED_LENGTH];
+    unsafe {
+        ext_ffi::casper_new_uref(uref_bytes.as_mut_ptr(), cl_value.as_ptr(), cl_value.len());
+    }
+    bytesrepr::deserialize(uref_bytes).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package_hash: Key = runtime::get_named_arg("package_hash");
+    let entrypoint: String = runtime::get_named_arg("entrypoint");
+    let args: Bytes = runtime::get_named_arg("args");
+
+    let result = call_versioned_contract_raw(package_hash, entrypoint, args);
+    let uref = if result.is_empty() {
+        storage::new_uref(())
+    } else {
+        new_uref_raw(&result)
+    };
+    runtime::put_key(RESULT, uref.into());
+}
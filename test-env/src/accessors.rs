@@ -0,0 +1,56 @@
+use casper_types::{Key, U256};
+
+use crate::{
+    keys::{key_and_value_to_str, key_to_base64, key_to_str, keys_to_str},
+    TestContract,
+};
+
+const BALANCES: &str = "balances";
+const ALLOWANCES: &str = "allowances";
+const OWNERS: &str = "owners";
+const OWNED_TOKENS_BY_INDEX: &str = "owned_tokens_by_index";
+
+pub fn erc20_balance_of(contract: &TestContract, owner: Key) -> U256 {
+    contract
+        .query_dictionary(BALANCES, key_to_str(&owner))
+        .unwrap_or_default()
+}
+
+pub fn erc20_allowance(contract: &TestContract, owner: Key, spender: Key) -> U256 {
+    contract
+        .query_dictionary(ALLOWANCES, keys_to_str(&owner, &spender))
+        .unwrap_or_default()
+}
+
+pub fn casper_erc20_balance_of(contract: &TestContract, owner: Key) -> U256 {
+    contract
+        .query_dictionary(BALANCES, key_to_base64(&owner))
+        .unwrap_or_default()
+}
+
+pub fn casper_erc20_allowance(contract: &TestContract, owner: Key, spender: Key) -> U256 {
+    contract
+        .query_dictionary(ALLOWANCES, keys_to_str(&owner, &spender))
+        .unwrap_or_default()
+}
+
+pub fn cep47_balance_of(contract: &TestContract, owner: Key) -> U256 {
+    contract
+        .query_dictionary(BALANCES, key_to_str(&owner))
+        .unwrap_or_default()
+}
+
+pub fn cep47_owner_of(contract: &TestContract, token_id: U256) -> Option<Key> {
+    contract.query_dictionary(OWNERS, token_id.to_string())
+}
+
+pub fn cep47_token_by_index(contract: &TestContract, owner: Key, index: U256) -> Option<U256> {
+    contract.query_dictionary(OWNED_TOKENS_BY_INDEX, key_and_value_to_str(&owner, &index))
+}
+
+pub fn cep47_tokens_of(contract: &TestContract, owner: Key) -> Vec<U256> {
+    let balance = cep47_balance_of(contract, owner).as_u64();
+    (0..balance)
+        .filter_map(|index| cep47_token_by_index(contract, owner, U256::from(index)))
+        .collect()
+}
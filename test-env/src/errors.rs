@@ -0,0 +1,14 @@
+use std::convert::TryFrom;
+
+use casper_types::ApiError;
+
+pub fn user_error_code(error: ApiError) -> Option<u16> {
+    match error {
+        ApiError::User(code) => Some(code),
+        _ => None,
+    }
+}
+
+pub fn user_error<E: TryFrom<u16>>(error: ApiError) -> Option<E> {
+    user_error_code(error).and_then(|code| E::try_from(code).ok())
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_execution_engine::shared::transform::Transform;
+use casper_types::{CLTyped, Key, StoredValue};
+
+pub type Event = BTreeMap<String, String>;
+
+const EVENT_TYPE: &str = "event_type";
+const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+// Events are the `BTreeMap<String, String>` values each crate writes with `storage::new_uref`,
+// read back from the execution journal so they keep the order they were emitted in.
+pub fn events_since(builder: &InMemoryWasmTestBuilder, deploy: usize) -> Vec<Event> {
+    let mut events = Vec::new();
+    for index in deploy..builder.get_exec_results_count() {
+        for result in builder.get_exec_result_owned(index).unwrap_or_default() {
+            let journal: Vec<(Key, Transform)> = result.execution_journal().clone().into();
+            for (key, transform) in journal {
+                if let (Key::URef(_), Transform::Write(StoredValue::CLValue(value))) =
+                    (key, transform)
+                {
+                    if *value.cl_type() != Event::cl_type() {
+                        continue;
+                    }
+                    let event: Event = value.into_t().expect("Wrong type in event.");
+                    if event.contains_key(EVENT_TYPE) {
+                        events.push(event);
+                    }
+                }
+            }
+        }
+    }
+    events
+}
+
+pub fn filter_by_package(events: Vec<Event>, package_hash: [u8; 32]) -> Vec<Event> {
+    let package_hash = hex::encode(package_hash);
+    events
+        .into_iter()
+        .filter(|event| {
+            event
+                .get(CONTRACT_PACKAGE_HASH)
+                .map_or(false, |hash| hash.ends_with(&package_hash))
+        })
+        .collect()
+}
+
+pub fn find_event<'a>(
+    events: &'a [Event],
+    event_type: &str,
+    fields: &[(&str, &str)],
+) -> Option<&'a Event> {
+    events.iter().find(|event| {
+        event.get(EVENT_TYPE).map(String::as_str) == Some(event_type)
+            && fields
+                .iter()
+                .all(|(name, value)| event.get(*name).map(String::as_str) == Some(*value))
+    })
+}
+
+pub fn assert_event_emitted(events: &[Event], event_type: &str, fields: &[(&str, &str)]) {
+    assert!(
+        find_event(events, event_type, fields).is_some(),
+        "{} with fields {:?} was not emitted, got {:?}",
+        event_type,
+        fields,
+        events
+    );
+}
@@ -0,0 +1,174 @@
+use std::{collections::BTreeMap, ops::Index};
+
+use casper_types::{account::AccountHash, Key, RuntimeArgs};
+
+use crate::{SnapshotId, TestContract, TestEnv};
+
+pub enum Ref {
+    PackageHash(String),
+    ContractHash(String),
+}
+
+enum StepKind {
+    Deploy { wasm: String },
+    Call { entry_point: String },
+}
+
+struct Step {
+    contract: String,
+    kind: StepKind,
+    sender: Option<AccountHash>,
+    args: RuntimeArgs,
+    refs: Vec<(String, Ref)>,
+}
+
+// Declares contracts to deploy and calls to make, in order. Reference args are filled in
+// with the hashes of contracts deployed by earlier steps.
+#[derive(Default)]
+pub struct Fixture {
+    steps: Vec<Step>,
+}
+
+impl Fixture {
+    pub fn new() -> Fixture {
+        Fixture::default()
+    }
+
+    pub fn deploy(mut self, name: &str, wasm: &str, args: RuntimeArgs) -> Self {
+        self.steps.push(Step {
+            contract: name.to_string(),
+            kind: StepKind::Deploy {
+                wasm: wasm.to_string(),
+            },
+            sender: None,
+            args,
+            refs: Vec::new(),
+        });
+        self
+    }
+
+    pub fn call(mut self, contract: &str, entry_point: &str, args: RuntimeArgs) -> Self {
+        self.steps.push(Step {
+            contract: contract.to_string(),
+            kind: StepKind::Call {
+                entry_point: entry_point.to_string(),
+            },
+            sender: None,
+            args,
+            refs: Vec::new(),
+        });
+        self
+    }
+
+    pub fn sender(mut self, sender: AccountHash) -> Self {
+        self.last_step().sender = Some(sender);
+        self
+    }
+
+    pub fn with_package_hash(mut self, arg: &str, contract: &str) -> Self {
+        self.last_step()
+            .refs
+            .push((arg.to_string(), Ref::PackageHash(contract.to_string())));
+        self
+    }
+
+    pub fn with_contract_hash(mut self, arg: &str, contract: &str) -> Self {
+        self.last_step()
+            .refs
+            .push((arg.to_string(), Ref::ContractHash(contract.to_string())));
+        self
+    }
+
+    pub fn build(&self, env: &TestEnv, sender: AccountHash) -> Deployed {
+        let mut contracts: BTreeMap<String, TestContract> = BTreeMap::new();
+        for step in &self.steps {
+            let mut args = step.args.clone();
+            for (arg, reference) in &step.refs {
+                let key = match reference {
+                    Ref::PackageHash(name) => Key::Hash(resolve(&contracts, name).package_hash()),
+                    Ref::ContractHash(name) => {
+                        Key::Hash(resolve(&contracts, name).contract_hash())
+                    }
+                };
+                args.insert(arg.clone(), key).unwrap();
+            }
+            let sender = step.sender.unwrap_or(sender);
+            match &step.kind {
+                StepKind::Deploy { wasm } => {
+                    let contract =
+                        TestContract::new(env, wasm, &step.contract, sender, args, env.block_time());
+                    contracts.insert(step.contract.clone(), contract);
+                }
+                StepKind::Call { entry_point } => {
+                    resolve(&contracts, &step.contract).call_contract(
+                        sender,
+                        entry_point,
+                        args,
+                        env.block_time(),
+                    );
+                }
+            }
+        }
+        Deployed {
+            env: env.clone(),
+            contracts,
+            snapshot: env.snapshot(),
+        }
+    }
+
+    fn last_step(&mut self) -> &mut Step {
+        self.steps
+            .last_mut()
+            .expect("Add a deploy or call step first.")
+    }
+}
+
+fn resolve<'a>(contracts: &'a BTreeMap<String, TestContract>, name: &str) -> &'a TestContract {
+    contracts
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not deployed by an earlier step.", name))
+}
+
+// The contracts of a built `Fixture`, along with a snapshot of the state right after setup.
+// Its handles only work in the `TestEnv` it was built in, use `fork` for an independent copy.
+pub struct Deployed {
+    env: TestEnv,
+    contracts: BTreeMap<String, TestContract>,
+    snapshot: SnapshotId,
+}
+
+impl Deployed {
+    pub fn env(&self) -> &TestEnv {
+        &self.env
+    }
+
+    pub fn contract(&self, name: &str) -> &TestContract {
+        resolve(&self.contracts, name)
+    }
+
+    pub fn reset(&self) {
+        self.env.restore(self.snapshot);
+    }
+
+    pub fn fork(&self) -> Deployed {
+        let env = self.env.fork_at(self.snapshot);
+        let contracts = self
+            .contracts
+            .iter()
+            .map(|(name, contract)| (name.clone(), contract.in_env(&env)))
+            .collect();
+        Deployed {
+            snapshot: env.snapshot(),
+            env,
+            contracts,
+        }
+    }
+}
+
+impl Index<&str> for Deployed {
+    type Output = TestContract;
+
+    fn index(&self, name: &str) -> &TestContract {
+        self.contract(name)
+    }
+}
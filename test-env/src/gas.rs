@@ -0,0 +1,52 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use casper_types::U512;
+
+// Budget files hold one `<entry point> <gas cost>` pair per line.
+#[derive(Clone)]
+pub struct GasBudget {
+    budgets: BTreeMap<String, U512>,
+    tolerance_percent: u64,
+}
+
+impl GasBudget {
+    pub fn load(path: &Path, tolerance_percent: u64) -> GasBudget {
+        let mut budgets = BTreeMap::new();
+        if let Ok(contents) = fs::read_to_string(path) {
+            for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                let (name, cost) = line
+                    .rsplit_once(' ')
+                    .unwrap_or_else(|| panic!("Malformed gas budget line: {}", line));
+                let cost = U512::from_dec_str(cost.trim())
+                    .unwrap_or_else(|_| panic!("Malformed gas budget line: {}", line));
+                budgets.insert(name.trim().to_string(), cost);
+            }
+        }
+        GasBudget {
+            budgets,
+            tolerance_percent,
+        }
+    }
+
+    pub fn check(&self, name: &str, cost: U512) {
+        if let Some(budget) = self.budgets.get(name) {
+            let limit = *budget * (100 + self.tolerance_percent) / 100;
+            assert!(
+                cost <= limit,
+                "{} used {} gas, more than {}% over its budget of {}",
+                name,
+                cost,
+                self.tolerance_percent,
+                budget
+            );
+        }
+    }
+}
+
+pub fn write_gas_report(path: &Path, report: &BTreeMap<String, U512>) {
+    let contents: String = report
+        .iter()
+        .map(|(name, cost)| format!("{} {}\n", name, cost))
+        .collect();
+    fs::write(path, contents).expect("Could not write gas report.");
+}
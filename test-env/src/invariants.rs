@@ -0,0 +1,484 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    accessors::{
+        cep47_balance_of, cep47_owner_of, cep47_token_by_index, erc20_allowance,
+        erc20_balance_of,
+    },
+    SnapshotId, TestContract, TestEnv,
+};
+
+// A sequence of random operations checked against invariants after every step. Each
+// sequence is replayed from a snapshot, which is also how failing sequences are shrunk.
+pub trait Harness {
+    type Op: Clone + Debug;
+
+    fn env(&self) -> &TestEnv;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Op;
+
+    // Runs `op`, which may legitimately revert, and checks invariants tied to that op.
+    fn apply(&self, op: &Self::Op) -> Result<(), String>;
+
+    // Checks invariants that must hold after every op.
+    fn check(&self) -> Result<(), String>;
+}
+
+pub fn run_sequence<H: Harness>(harness: &H, ops: &[H::Op]) -> Result<(), String> {
+    for (step, op) in ops.iter().enumerate() {
+        harness
+            .apply(op)
+            .and_then(|_| harness.check())
+            .map_err(|message| format!("step {} ({:?}): {}", step, op, message))?;
+    }
+    Ok(())
+}
+
+pub fn check_invariants<H: Harness>(harness: &H, seed: u64, steps: usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ops: Vec<H::Op> = (0..steps).map(|_| harness.generate(&mut rng)).collect();
+    let start = harness.env().snapshot();
+
+    if run_sequence(harness, &ops).is_ok() {
+        return;
+    }
+    let ops = shrink(harness, start, ops);
+    harness.env().restore(start);
+    let message = run_sequence(harness, &ops).unwrap_err();
+    panic!(
+        "Invariant violated with seed {}, minimal sequence {:?}: {}",
+        seed, ops, message
+    );
+}
+
+// Removes chunks of decreasing size for as long as the sequence keeps failing.
+fn shrink<H: Harness>(harness: &H, start: SnapshotId, mut ops: Vec<H::Op>) -> Vec<H::Op> {
+    let mut chunk = ops.len() / 2;
+    while chunk > 0 {
+        let mut index = 0;
+        while index < ops.len() {
+            let mut candidate = ops.clone();
+            candidate.drain(index..(index + chunk).min(ops.len()));
+            harness.env().restore(start);
+            if run_sequence(harness, &candidate).is_err() {
+                ops = candidate;
+            } else {
+                index += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    ops
+}
+
+// Compares a call's outcome with the one predicted from the state before it, `None`
+// when the contract's configuration decides.
+fn expect_outcome(
+    entry_point: &str,
+    succeeded: bool,
+    expected: Option<bool>,
+) -> Result<(), String> {
+    match (expected, succeeded) {
+        (Some(true), false) => Err(format!("{} reverted but should succeed", entry_point)),
+        (Some(false), true) => Err(format!("{} succeeded but should revert", entry_point)),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Erc20Op {
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: U256,
+    },
+    Approve {
+        owner: usize,
+        spender: usize,
+        amount: U256,
+    },
+    TransferFrom {
+        spender: usize,
+        owner: usize,
+        recipient: usize,
+        amount: U256,
+    },
+    Mint {
+        to: usize,
+        amount: U256,
+    },
+    Burn {
+        from: usize,
+        amount: U256,
+    },
+}
+
+pub struct Erc20Harness {
+    env: TestEnv,
+    token: TestContract,
+    minter: AccountHash,
+    users: Vec<AccountHash>,
+    max_amount: u64,
+    balance_of: fn(&TestContract, Key) -> U256,
+    allowance: fn(&TestContract, Key, Key) -> U256,
+}
+
+impl Erc20Harness {
+    // `minter` must hold any initial supply, every other holder has to be in `users`.
+    pub fn new(
+        env: &TestEnv,
+        token: TestContract,
+        minter: AccountHash,
+        users: Vec<AccountHash>,
+    ) -> Erc20Harness {
+        Erc20Harness {
+            env: env.clone(),
+            token,
+            minter,
+            users,
+            max_amount: 1_000,
+            balance_of: erc20_balance_of,
+            allowance: erc20_allowance,
+        }
+    }
+
+    pub fn max_amount(mut self, max_amount: u64) -> Self {
+        self.max_amount = max_amount;
+        self
+    }
+
+    pub fn accessors(
+        mut self,
+        balance_of: fn(&TestContract, Key) -> U256,
+        allowance: fn(&TestContract, Key, Key) -> U256,
+    ) -> Self {
+        self.balance_of = balance_of;
+        self.allowance = allowance;
+        self
+    }
+
+    pub fn token(&self) -> &TestContract {
+        &self.token
+    }
+
+    fn user(&self, index: usize) -> Key {
+        Key::Account(self.users[index])
+    }
+
+    fn call(&self, sender: AccountHash, entry_point: &str, args: RuntimeArgs) -> bool {
+        self.token
+            .try_call_contract(sender, entry_point, args, self.env.block_time())
+    }
+}
+
+impl Harness for Erc20Harness {
+    type Op = Erc20Op;
+
+    fn env(&self) -> &TestEnv {
+        &self.env
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> Erc20Op {
+        let users = self.users.len();
+        let amount = U256::from(rng.gen_range(0..=self.max_amount));
+        match rng.gen_range(0..5) {
+            0 => Erc20Op::Transfer {
+                from: rng.gen_range(0..users),
+                to: rng.gen_range(0..users),
+                amount,
+            },
+            1 => Erc20Op::Approve {
+                owner: rng.gen_range(0..users),
+                spender: rng.gen_range(0..users),
+                amount,
+            },
+            2 => Erc20Op::TransferFrom {
+                spender: rng.gen_range(0..users),
+                owner: rng.gen_range(0..users),
+                recipient: rng.gen_range(0..users),
+                amount,
+            },
+            3 => Erc20Op::Mint {
+                to: rng.gen_range(0..users),
+                amount,
+            },
+            _ => Erc20Op::Burn {
+                from: rng.gen_range(0..users),
+                amount,
+            },
+        }
+    }
+
+    fn apply(&self, op: &Erc20Op) -> Result<(), String> {
+        match *op {
+            Erc20Op::Transfer { from, to, amount } => {
+                let funded = (self.balance_of)(&self.token, self.user(from)) >= amount;
+                let succeeded = self.call(
+                    self.users[from],
+                    "transfer",
+                    runtime_args! {"recipient" => self.user(to), "amount" => amount},
+                );
+                expect_outcome("transfer", succeeded, Some(funded))?;
+            }
+            Erc20Op::Approve {
+                owner,
+                spender,
+                amount,
+            } => {
+                let succeeded = self.call(
+                    self.users[owner],
+                    "approve",
+                    runtime_args! {"spender" => self.user(spender), "amount" => amount},
+                );
+                expect_outcome("approve", succeeded, Some(true))?;
+            }
+            Erc20Op::TransferFrom {
+                spender,
+                owner,
+                recipient,
+                amount,
+            } => {
+                let before = (self.allowance)(&self.token, self.user(owner), self.user(spender));
+                let funded = (self.balance_of)(&self.token, self.user(owner)) >= amount;
+                let succeeded = self.call(
+                    self.users[spender],
+                    "transfer_from",
+                    runtime_args! {
+                        "owner" => self.user(owner),
+                        "recipient" => self.user(recipient),
+                        "amount" => amount
+                    },
+                );
+                if owner != spender {
+                    expect_outcome("transfer_from", succeeded, Some(funded && before >= amount))?;
+                }
+                let after = (self.allowance)(&self.token, self.user(owner), self.user(spender));
+                if owner != spender && after > before {
+                    return Err(format!("allowance grew from {} to {}", before, after));
+                }
+            }
+            Erc20Op::Mint { to, amount } => {
+                let succeeded = self.call(
+                    self.minter,
+                    "mint",
+                    runtime_args! {"to" => self.user(to), "amount" => amount},
+                );
+                expect_outcome("mint", succeeded, Some(true))?;
+            }
+            Erc20Op::Burn { from, amount } => {
+                let funded = (self.balance_of)(&self.token, self.user(from)) >= amount;
+                let succeeded = self.call(
+                    self.minter,
+                    "burn",
+                    runtime_args! {"from" => self.user(from), "amount" => amount},
+                );
+                expect_outcome("burn", succeeded, Some(funded))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let mut holders: Vec<Key> = self.users.iter().map(|user| Key::Account(*user)).collect();
+        if !self.users.contains(&self.minter) {
+            holders.push(Key::Account(self.minter));
+        }
+        let balances = holders.into_iter().fold(U256::zero(), |sum, holder| {
+            sum + (self.balance_of)(&self.token, holder)
+        });
+        let total_supply: U256 = self.token.query_contract_named_key("total_supply");
+        if balances != total_supply {
+            return Err(format!(
+                "sum of balances {} differs from total supply {}",
+                balances, total_supply
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Cep47Op {
+    Mint {
+        to: usize,
+        token_id: U256,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        token_id: U256,
+    },
+    Burn {
+        owner: usize,
+        token_id: U256,
+    },
+    Approve {
+        owner: usize,
+        spender: usize,
+        token_id: U256,
+    },
+}
+
+pub struct Cep47Harness {
+    env: TestEnv,
+    token: TestContract,
+    minter: AccountHash,
+    users: Vec<AccountHash>,
+    max_token_id: u64,
+}
+
+impl Cep47Harness {
+    pub fn new(
+        env: &TestEnv,
+        token: TestContract,
+        minter: AccountHash,
+        users: Vec<AccountHash>,
+    ) -> Cep47Harness {
+        Cep47Harness {
+            env: env.clone(),
+            token,
+            minter,
+            users,
+            max_token_id: 20,
+        }
+    }
+
+    pub fn max_token_id(mut self, max_token_id: u64) -> Self {
+        self.max_token_id = max_token_id;
+        self
+    }
+
+    pub fn token(&self) -> &TestContract {
+        &self.token
+    }
+
+    fn user(&self, index: usize) -> Key {
+        Key::Account(self.users[index])
+    }
+
+    fn call(&self, sender: AccountHash, entry_point: &str, args: RuntimeArgs) -> bool {
+        self.token
+            .try_call_contract(sender, entry_point, args, self.env.block_time())
+    }
+}
+
+impl Harness for Cep47Harness {
+    type Op = Cep47Op;
+
+    fn env(&self) -> &TestEnv {
+        &self.env
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> Cep47Op {
+        let users = self.users.len();
+        let token_id = U256::from(rng.gen_range(0..=self.max_token_id));
+        match rng.gen_range(0..4) {
+            0 => Cep47Op::Mint {
+                to: rng.gen_range(0..users),
+                token_id,
+            },
+            1 => Cep47Op::Transfer {
+                from: rng.gen_range(0..users),
+                to: rng.gen_range(0..users),
+                token_id,
+            },
+            2 => Cep47Op::Burn {
+                owner: rng.gen_range(0..users),
+                token_id,
+            },
+            _ => Cep47Op::Approve {
+                owner: rng.gen_range(0..users),
+                spender: rng.gen_range(0..users),
+                token_id,
+            },
+        }
+    }
+
+    fn apply(&self, op: &Cep47Op) -> Result<(), String> {
+        match *op {
+            Cep47Op::Mint { to, token_id } => {
+                let unminted = cep47_owner_of(&self.token, token_id).is_none();
+                let succeeded = self.call(
+                    self.minter,
+                    "mint",
+                    runtime_args! {
+                        "recipient" => self.user(to),
+                        "token_ids" => Some(vec![token_id]),
+                        "token_metas" => vec![BTreeMap::<String, String>::new()]
+                    },
+                );
+                expect_outcome("mint", succeeded, Some(unminted))?;
+            }
+            Cep47Op::Transfer { from, to, token_id } => {
+                let owned = cep47_owner_of(&self.token, token_id) == Some(self.user(from));
+                let succeeded = self.call(
+                    self.users[from],
+                    "transfer",
+                    runtime_args! {"recipient" => self.user(to), "token_ids" => vec![token_id]},
+                );
+                // The transfer policy may still forbid moving an owned token.
+                expect_outcome(
+                    "transfer",
+                    succeeded,
+                    if owned { None } else { Some(false) },
+                )?;
+            }
+            Cep47Op::Burn { owner, token_id } => {
+                let owned = cep47_owner_of(&self.token, token_id) == Some(self.user(owner));
+                let succeeded = self.call(
+                    self.users[owner],
+                    "burn",
+                    runtime_args! {"owner" => self.user(owner), "token_ids" => vec![token_id]},
+                );
+                expect_outcome("burn", succeeded, Some(owned))?;
+            }
+            Cep47Op::Approve {
+                owner,
+                spender,
+                token_id,
+            } => {
+                let owned = cep47_owner_of(&self.token, token_id) == Some(self.user(owner));
+                let succeeded = self.call(
+                    self.users[owner],
+                    "approve",
+                    runtime_args! {"spender" => self.user(spender), "token_ids" => vec![token_id]},
+                );
+                expect_outcome("approve", succeeded, Some(owned))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let mut balances = U256::zero();
+        for user in &self.users {
+            let owner = Key::Account(*user);
+            let balance = cep47_balance_of(&self.token, owner);
+            balances += balance;
+            for index in 0..balance.as_u64() {
+                let index = U256::from(index);
+                let token_id = cep47_token_by_index(&self.token, owner, index)
+                    .ok_or_else(|| format!("{} has no token at index {}", owner, index))?;
+                if cep47_owner_of(&self.token, token_id) != Some(owner) {
+                    return Err(format!(
+                        "token {} is indexed for {} but owned by {:?}",
+                        token_id,
+                        owner,
+                        cep47_owner_of(&self.token, token_id)
+                    ));
+                }
+            }
+        }
+        let total_supply: U256 = self.token.query_contract_named_key("total_supply");
+        if balances != total_supply {
+            return Err(format!(
+                "sum of balances {} differs from total supply {}",
+                balances, total_supply
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+// Native versions of the dictionary item key encodings used on-chain, kept in sync with
+// `casperlabs-contract-utils`, `casper-erc20-crate` and `casperlabs-curve-token-utils`.
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
+use casper_types::{bytesrepr::ToBytes, Key};
+use tiny_keccak::{Hasher, Keccak};
+
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(data);
+    hasher.finalize_variable(|hash| result.copy_from_slice(hash));
+    result
+}
+
+pub fn keccak256<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut result);
+    result
+}
+
+pub fn key_to_str(key: &Key) -> String {
+    match key {
+        Key::Account(account) => account.to_string(),
+        Key::Hash(package) => hex::encode(package),
+        _ => panic!("Unexpected key variant."),
+    }
+}
+
+pub fn keys_to_str<U: ToBytes, V: ToBytes>(key_a: &U, key_b: &V) -> String {
+    let mut bytes_a = key_a.to_bytes().unwrap();
+    let mut bytes_b = key_b.to_bytes().unwrap();
+    bytes_a.append(&mut bytes_b);
+    hex::encode(blake2b(bytes_a))
+}
+
+pub fn values_to_str<T: ToBytes, U: ToBytes>(value_a: &T, value_b: &U) -> String {
+    keys_to_str(value_a, value_b)
+}
+
+pub fn key_and_value_to_str<T: ToBytes>(key: &Key, value: &T) -> String {
+    let mut bytes_a = key.to_bytes().unwrap();
+    let mut bytes_b = value.to_bytes().unwrap();
+
+    bytes_a.append(&mut bytes_b);
+
+    hex::encode(blake2b(bytes_a))
+}
+
+// `casper-erc20-crate` balances, where an `Address` serializes like the `Key` it wraps.
+pub fn key_to_base64(key: &Key) -> String {
+    base64::encode(&key.to_bytes().unwrap())
+}
+
+// `casperlabs-curve-token-utils::key_gen::generate_key_for_dictionary`.
+pub fn generate_key_for_dictionary(key: &Key, id: &[u32]) -> String {
+    let key_str = format!("{}{:?}", key, id);
+    hex::encode(keccak256(key_str.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{account::AccountHash, U256};
+
+    // Vectors follow the `casper-types` serialization of `Key` (tag byte then the 32 byte
+    // hash) and `U256` (length byte then little-endian bytes) hashed the way the contracts do.
+    fn account() -> Key {
+        Key::Account(AccountHash::new([1u8; 32]))
+    }
+
+    fn package() -> Key {
+        Key::Hash([2u8; 32])
+    }
+
+    #[test]
+    fn hashes_match_reference_digests() {
+        assert_eq!(
+            hex::encode(blake2b(b"")),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn key_to_str_matches_contract_utils() {
+        assert_eq!(key_to_str(&account()), "01".repeat(32));
+        assert_eq!(key_to_str(&package()), "02".repeat(32));
+    }
+
+    #[test]
+    fn keys_to_str_matches_contract_utils() {
+        assert_eq!(
+            keys_to_str(&account(), &package()),
+            "7936de67b8d98ef5822d69f323dfd28e92ff8263b04314e293b9bd9f08ff94f0"
+        );
+    }
+
+    #[test]
+    fn values_to_str_matches_contract_utils() {
+        assert_eq!(
+            values_to_str(&U256::from(1), &U256::from(2)),
+            "1c917ac6ec9f72b60f303e73ed8d1c5c65b69ae73a75098fb8c99897a5cde813"
+        );
+    }
+
+    #[test]
+    fn key_and_value_to_str_matches_contract_utils() {
+        assert_eq!(
+            key_and_value_to_str(&account(), &U256::from(1)),
+            "e1cdb43d51a33c869cb6dac9e00ff6d857e00bfd7fe92fc73eadd2866e620c15"
+        );
+    }
+
+    #[test]
+    fn key_to_base64_matches_casper_erc20() {
+        assert_eq!(
+            key_to_base64(&account()),
+            "AAEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEB"
+        );
+    }
+
+    #[test]
+    fn generate_key_for_dictionary_matches_curve_token_utils() {
+        assert_eq!(
+            generate_key_for_dictionary(&account(), &[1, 2]),
+            "18ef631e39d957ee1cb18f7bedd1649a7590022a8018e5a0c0b3e76bda43db72"
+        );
+    }
+}
@@ -1,8 +1,30 @@
+mod accessors;
+mod errors;
+mod events;
+mod fixtures;
+mod gas;
+mod invariants;
+mod keys;
 mod test_contract;
 mod test_env;
 mod utils;
 use crate::test_env as other_test_env;
 
-pub use other_test_env::{now, TestEnv};
-pub use test_contract::TestContract;
+pub use accessors::{
+    casper_erc20_allowance, casper_erc20_balance_of, cep47_balance_of, cep47_owner_of,
+    cep47_token_by_index, cep47_tokens_of, erc20_allowance, erc20_balance_of,
+};
+pub use errors::{user_error, user_error_code};
+pub use events::{assert_event_emitted, find_event, Event};
+pub use fixtures::{Deployed, Fixture, Ref};
+pub use invariants::{
+    check_invariants, run_sequence, Cep47Harness, Cep47Op, Erc20Harness, Erc20Op, Harness,
+};
+pub use keys::{
+    blake2b, generate_key_for_dictionary, keccak256, key_and_value_to_str, key_to_base64,
+    key_to_str, keys_to_str, values_to_str,
+};
+pub use other_test_env::{now, KeyType, SnapshotId, TestEnv, TestEnvBuilder};
+pub use test_contract::{TestContract, SESSION_PROXY_WASM};
+pub use utils::DeploySource;
 pub use test_contract::{call_contract_with_contract_hash, call_contract_with_package_hash};
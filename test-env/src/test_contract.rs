@@ -1,16 +1,42 @@
-use std::path::PathBuf;
+use std::{env, fs, path::PathBuf};
 
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, CLTyped, ContractHash, ContractPackageHash,
-    RuntimeArgs,
+    account::AccountHash,
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    runtime_args, ApiError, CLTyped, ContractHash, ContractPackageHash, Key, RuntimeArgs,
 };
 
-use crate::{utils::DeploySource, TestEnv};
+use crate::{
+    events::{self, Event},
+    utils::DeploySource,
+    TestEnv,
+};
+
+// Built from `session-proxy/` with `cargo build --release --target wasm32-unknown-unknown`,
+// it stores the return value of the called entry point under the sender's `result` named
+// key. Loaded at runtime from `SESSION_PROXY_WASM_PATH`, or this file name when unset.
+pub const SESSION_PROXY_WASM: &str = "session-proxy.wasm";
+const SESSION_PROXY_WASM_PATH: &str = "SESSION_PROXY_WASM_PATH";
+const RESULT: &str = "result";
+
+fn session_proxy_wasm() -> Vec<u8> {
+    let path = env::var(SESSION_PROXY_WASM_PATH)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(SESSION_PROXY_WASM));
+    fs::read(&path).unwrap_or_else(|error| {
+        panic!(
+            "Cannot read the session proxy at {}: {}",
+            path.display(),
+            error
+        )
+    })
+}
 
 pub struct TestContract {
     env: TestEnv,
     name: String,
     contract_owner: AccountHash,
+    hashes: Option<(ContractHash, ContractPackageHash)>,
 }
 
 impl TestContract {
@@ -30,6 +56,31 @@ impl TestContract {
             env: env.clone(),
             name: String::from(name),
             contract_owner: sender,
+            hashes: None,
+        }
+    }
+
+    pub fn from_hashes(
+        env: &TestEnv,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) -> TestContract {
+        TestContract {
+            env: env.clone(),
+            name: String::new(),
+            contract_owner: AccountHash::default(),
+            hashes: Some((contract_hash, package_hash)),
+        }
+    }
+
+    // Same contract, name and owner, read and called through `env`, which must share this
+    // contract's state (e.g. a fork of the original environment).
+    pub(crate) fn in_env(&self, env: &TestEnv) -> TestContract {
+        TestContract {
+            env: env.clone(),
+            name: self.name.clone(),
+            contract_owner: self.contract_owner,
+            hashes: self.hashes,
         }
     }
 
@@ -47,17 +98,37 @@ impl TestContract {
     }
 
     pub fn query_named_key<T: CLTyped + FromBytes>(&self, key: String) -> T {
+        // Handles from `from_hashes` have no deployer account to read the key through.
+        if self.hashes.is_some() {
+            return self.query_contract_named_key(&key);
+        }
         let contract_name = format!("{}_contract_hash", self.name);
         self.env
             .query_account_named_key(self.contract_owner, &[contract_name, key])
     }
 
+    pub fn query_contract_named_key<T: CLTyped + FromBytes>(&self, key: &str) -> T {
+        self.env
+            .query_contract_named_key(self.contract_hash(), &[key.to_string()])
+    }
+
+    pub fn query_package_named_key<T: CLTyped + FromBytes>(&self, key: &str) -> T {
+        self.env
+            .query_package_named_key(self.package_hash(), &[key.to_string()])
+    }
+
     pub fn contract_hash(&self) -> [u8; 32] {
+        if let Some((contract_hash, _)) = self.hashes {
+            return contract_hash.value();
+        }
         let key = format!("{}_contract_hash_wrapped", self.name);
         self.env
             .query_account_named_key(self.contract_owner, &[key])
     }
     pub fn package_hash(&self) -> [u8; 32] {
+        if let Some((_, package_hash)) = self.hashes {
+            return package_hash.value();
+        }
         let key = format!("{}_package_hash_wrapped", self.name);
         self.env
             .query_account_named_key(self.contract_owner, &[key])
@@ -76,6 +147,93 @@ impl TestContract {
         };
         self.env.run(sender, session_code, session_args, time);
     }
+
+    pub fn call(&self, sender: AccountHash, entry_point: &str, session_args: RuntimeArgs) {
+        let session_code = DeploySource::ByContractHash {
+            hash: ContractHash::new(self.contract_hash()),
+            method: entry_point.to_string(),
+        };
+        self.env.run_now(sender, session_code, session_args);
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        events::filter_by_package(self.env.last_events(), self.package_hash())
+    }
+
+    pub fn events_since(&self, deploy: usize) -> Vec<Event> {
+        events::filter_by_package(self.env.events_since(deploy), self.package_hash())
+    }
+
+    pub fn assert_event_emitted(&self, event_type: &str, fields: &[(&str, &str)]) {
+        events::assert_event_emitted(&self.events(), event_type, fields);
+    }
+
+    pub fn call_contract_with_result<T: CLTyped + FromBytes>(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        time: u64,
+    ) -> T {
+        let args = runtime_args! {
+            "package_hash" => Key::Hash(self.package_hash()),
+            "entrypoint" => entry_point.to_string(),
+            "args" => Bytes::from(session_args.to_bytes().unwrap()),
+        };
+        // Gas is reported under the called entry point rather than the proxy.
+        let session_code = DeploySource::Bytes {
+            module_bytes: session_proxy_wasm(),
+            name: entry_point.to_string(),
+        };
+        self.env.run(sender, session_code, args, time);
+        self.env
+            .query_account_named_key(sender, &[RESULT.to_string()])
+    }
+
+    pub fn try_call_contract(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        time: u64,
+    ) -> bool {
+        let session_code = DeploySource::ByContractHash {
+            hash: ContractHash::new(self.contract_hash()),
+            method: entry_point.to_string(),
+        };
+        self.env.try_run(sender, session_code, session_args, time)
+    }
+
+    pub fn call_contract_expect_failure(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        time: u64,
+    ) -> ApiError {
+        let session_code = DeploySource::ByContractHash {
+            hash: ContractHash::new(self.contract_hash()),
+            method: entry_point.to_string(),
+        };
+        self.env
+            .run_expect_failure(sender, session_code, session_args, time)
+    }
+
+    pub fn call_contract_expect_error(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        time: u64,
+        expected: ApiError,
+    ) {
+        let error = self.call_contract_expect_failure(sender, entry_point, session_args, time);
+        assert_eq!(
+            error, expected,
+            "{} reverted with an unexpected error",
+            entry_point
+        );
+    }
 }
 pub fn call_contract_with_contract_hash(
     env: &TestEnv,
@@ -1,14 +1,26 @@
 use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::SystemTime,
 };
 
 use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey,
+    account::AccountHash, bytesrepr::FromBytes, ApiError, CLTyped, Key, PublicKey, RuntimeArgs,
+    SecretKey, StoredValue, U512,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::utils::{deploy, fund_account, query, query_dictionary_item, DeploySource};
+use crate::{
+    events::{self, Event},
+    gas::{write_gas_report, GasBudget},
+    keys::blake2b,
+    utils::{
+        deploy, fund_account, query, query_dictionary_item, revert_error, DeploySource,
+        DEFAULT_ACCOUNT_BALANCE,
+    },
+};
 
 pub fn now() -> u64 {
     SystemTime::now()
@@ -24,9 +36,11 @@ pub struct TestEnv {
 
 impl TestEnv {
     pub fn new() -> TestEnv {
-        TestEnv {
-            state: Arc::new(Mutex::new(TestEnvState::new())),
-        }
+        TestEnv::builder().build()
+    }
+
+    pub fn builder() -> TestEnvBuilder {
+        TestEnvBuilder::default()
     }
 
     pub fn run(
@@ -36,20 +50,120 @@ impl TestEnv {
         session_args: RuntimeArgs,
         time: u64,
     ) {
-        deploy(
-            &mut self.state.lock().unwrap().builder,
-            &sender,
-            &session_code,
-            session_args,
-            true,
-            Some(time),
-        )
+        self.state
+            .lock()
+            .unwrap()
+            .run(sender, session_code, session_args, Some(true), Some(time))
+    }
+
+    // Runs at the managed clock, see `block_time` and `advance_time`.
+    pub fn run_now(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .run(sender, session_code, session_args, Some(true), None)
+    }
+
+    pub fn try_run(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+        time: u64,
+    ) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.run(sender, session_code, session_args, None, Some(time));
+        !state.builder.is_error()
+    }
+
+    pub fn run_expect_failure(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+        time: u64,
+    ) -> ApiError {
+        let mut state = self.state.lock().unwrap();
+        state.run(sender, session_code, session_args, Some(false), Some(time));
+        revert_error(&state.builder).expect("Deploy failed without reverting.")
+    }
+
+    pub fn last_error(&self) -> Option<ApiError> {
+        revert_error(&self.state.lock().unwrap().builder)
     }
 
     pub fn next_user(&self) -> AccountHash {
         self.state.lock().unwrap().next_user()
     }
 
+    pub fn block_time(&self) -> u64 {
+        self.state.lock().unwrap().block_time
+    }
+
+    pub fn set_block_time(&self, time: u64) {
+        self.state.lock().unwrap().block_time = time;
+    }
+
+    pub fn advance_time(&self, ms: u64) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        state.block_time += ms;
+        state.block_time
+    }
+
+    pub fn snapshot(&self) -> SnapshotId {
+        self.state.lock().unwrap().snapshot()
+    }
+
+    pub fn restore(&self, id: SnapshotId) {
+        self.state.lock().unwrap().restore(id)
+    }
+
+    pub fn fork(&self) -> TestEnv {
+        let mut state = self.state.lock().unwrap().clone();
+        state.snapshots.clear();
+        TestEnv {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn fork_at(&self, id: SnapshotId) -> TestEnv {
+        let mut state = self.state.lock().unwrap().clone();
+        state.restore(id);
+        state.snapshots.clear();
+        TestEnv {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn last_gas_cost(&self) -> U512 {
+        self.state.lock().unwrap().last_gas_cost
+    }
+
+    pub fn gas_report(&self) -> BTreeMap<String, U512> {
+        self.state.lock().unwrap().gas_report.clone()
+    }
+
+    pub fn save_gas_report<P: AsRef<Path>>(&self, path: P) {
+        write_gas_report(path.as_ref(), &self.state.lock().unwrap().gas_report);
+    }
+
+    pub fn deploy_count(&self) -> usize {
+        self.state.lock().unwrap().builder.get_exec_results_count()
+    }
+
+    pub fn events_since(&self, deploy: usize) -> Vec<Event> {
+        events::events_since(&self.state.lock().unwrap().builder, deploy)
+    }
+
+    pub fn last_events(&self) -> Vec<Event> {
+        self.events_since(self.deploy_count().saturating_sub(1))
+    }
+
     pub fn query<T: CLTyped + FromBytes>(
         &self,
         contract_hash: [u8; 32],
@@ -84,6 +198,34 @@ impl TestEnv {
             .unwrap()
             .query_account_named_key(account, path)
     }
+
+    pub fn query_contract_named_key<T: CLTyped + FromBytes>(
+        &self,
+        contract_hash: [u8; 32],
+        path: &[String],
+    ) -> T {
+        self.state
+            .lock()
+            .unwrap()
+            .query_contract_named_key(contract_hash, path)
+    }
+
+    pub fn query_package_named_key<T: CLTyped + FromBytes>(
+        &self,
+        package_hash: [u8; 32],
+        path: &[String],
+    ) -> T {
+        let state = self.state.lock().unwrap();
+        let contract_hash = state.current_contract_hash(package_hash);
+        state.query_contract_named_key(contract_hash, path)
+    }
+
+    pub fn current_contract_hash(&self, package_hash: [u8; 32]) -> [u8; 32] {
+        self.state
+            .lock()
+            .unwrap()
+            .current_contract_hash(package_hash)
+    }
 }
 
 impl Default for TestEnv {
@@ -92,33 +234,157 @@ impl Default for TestEnv {
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum KeyType {
+    Ed25519,
+    Secp256k1,
+}
+
+pub struct TestEnvBuilder {
+    accounts: usize,
+    key_type: KeyType,
+    default_balance: U512,
+    balances: BTreeMap<usize, U512>,
+    seed: Option<u64>,
+    block_time: Option<u64>,
+    gas_budget: Option<(PathBuf, u64)>,
+}
+
+impl Default for TestEnvBuilder {
+    fn default() -> Self {
+        TestEnvBuilder {
+            accounts: 10,
+            key_type: KeyType::Ed25519,
+            default_balance: U512::from(DEFAULT_ACCOUNT_BALANCE),
+            balances: BTreeMap::new(),
+            seed: None,
+            block_time: None,
+            gas_budget: None,
+        }
+    }
+}
+
+impl TestEnvBuilder {
+    pub fn accounts(mut self, accounts: usize) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    pub fn key_type(mut self, key_type: KeyType) -> Self {
+        self.key_type = key_type;
+        self
+    }
+
+    pub fn default_balance(mut self, balance: U512) -> Self {
+        self.default_balance = balance;
+        self
+    }
+
+    pub fn balance(mut self, account_index: usize, balance: U512) -> Self {
+        self.balances.insert(account_index, balance);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn block_time(mut self, time: u64) -> Self {
+        self.block_time = Some(time);
+        self
+    }
+
+    pub fn gas_budget<P: AsRef<Path>>(mut self, path: P, tolerance_percent: u64) -> Self {
+        self.gas_budget = Some((path.as_ref().to_path_buf(), tolerance_percent));
+        self
+    }
+
+    pub fn build(self) -> TestEnv {
+        TestEnv {
+            state: Arc::new(Mutex::new(TestEnvState::new(self))),
+        }
+    }
+}
+
+fn secret_key(key_type: KeyType, index: usize) -> SecretKey {
+    match key_type {
+        KeyType::Ed25519 if index <= u8::MAX as usize => {
+            SecretKey::ed25519_from_bytes([index as u8; 32])
+        }
+        KeyType::Ed25519 => SecretKey::ed25519_from_bytes(blake2b(index.to_le_bytes())),
+        KeyType::Secp256k1 => SecretKey::secp256k1_from_bytes(blake2b(index.to_le_bytes())),
+    }
+    .unwrap()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotId(usize);
+
+// Cloning the builder keeps its post-state hash while sharing the in-memory global state,
+// so a snapshot is cheap and restoring it rolls every later commit back.
+#[derive(Clone)]
+struct Snapshot {
+    builder: InMemoryWasmTestBuilder,
+    accounts: Vec<AccountHash>,
+    block_time: u64,
+}
+
+#[derive(Clone)]
 struct TestEnvState {
     builder: InMemoryWasmTestBuilder,
     accounts: Vec<AccountHash>,
+    rng: StdRng,
+    block_time: u64,
+    last_gas_cost: U512,
+    gas_report: BTreeMap<String, U512>,
+    gas_budget: Option<GasBudget>,
+    snapshots: Vec<Snapshot>,
 }
 
 impl TestEnvState {
-    pub fn new() -> TestEnvState {
+    pub fn new(config: TestEnvBuilder) -> TestEnvState {
         let mut builder = InMemoryWasmTestBuilder::default();
         builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let mut rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
         let mut accounts = Vec::new();
-        for i in 0..10u8 {
-            let secret_key: SecretKey = SecretKey::ed25519_from_bytes([i; 32]).unwrap();
+        for i in 0..config.accounts {
+            let secret_key: SecretKey = secret_key(config.key_type, i);
             let public_key: PublicKey = (&secret_key).into();
             let account_hash = AccountHash::from(&public_key);
+            let balance = config
+                .balances
+                .get(&i)
+                .copied()
+                .unwrap_or(config.default_balance);
             accounts.push(account_hash);
             builder
-                .exec(fund_account(&account_hash))
+                .exec(fund_account(&account_hash, balance, rng.gen()))
                 .expect_success()
                 .commit();
         }
 
-        TestEnvState { builder, accounts }
+        TestEnvState {
+            builder,
+            accounts,
+            rng,
+            block_time: config.block_time.unwrap_or_else(now),
+            last_gas_cost: U512::zero(),
+            gas_report: BTreeMap::new(),
+            gas_budget: config
+                .gas_budget
+                .map(|(path, tolerance_percent)| GasBudget::load(&path, tolerance_percent)),
+            snapshots: Vec::new(),
+        }
     }
 
     pub fn _new_with_users(user_secrets: &[[u8; 32]]) -> TestEnvState {
         let mut builder = InMemoryWasmTestBuilder::default();
         builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let mut rng = StdRng::from_entropy();
 
         let mut accounts = Vec::new();
         for user_secret in user_secrets {
@@ -127,32 +393,81 @@ impl TestEnvState {
             let account_hash = AccountHash::from(&public_key);
             accounts.push(account_hash);
             builder
-                .exec(fund_account(&account_hash))
+                .exec(fund_account(
+                    &account_hash,
+                    U512::from(DEFAULT_ACCOUNT_BALANCE),
+                    rng.gen(),
+                ))
                 .expect_success()
                 .commit();
         }
 
-        TestEnvState { builder, accounts }
+        TestEnvState {
+            builder,
+            accounts,
+            rng,
+            block_time: now(),
+            last_gas_cost: U512::zero(),
+            gas_report: BTreeMap::new(),
+            gas_budget: None,
+            snapshots: Vec::new(),
+        }
     }
 
     pub fn next_user(&mut self) -> AccountHash {
-        self.accounts.pop().unwrap()
+        self.accounts
+            .pop()
+            .expect("No accounts left, configure more with TestEnvBuilder::accounts.")
+    }
+
+    pub fn snapshot(&mut self) -> SnapshotId {
+        self.snapshots.push(Snapshot {
+            builder: self.builder.clone(),
+            accounts: self.accounts.clone(),
+            block_time: self.block_time,
+        });
+        SnapshotId(self.snapshots.len() - 1)
+    }
+
+    pub fn restore(&mut self, id: SnapshotId) {
+        let snapshot = self.snapshots.get(id.0).expect("Unknown snapshot.").clone();
+        self.builder = snapshot.builder;
+        self.accounts = snapshot.accounts;
+        self.block_time = snapshot.block_time;
     }
 
-    pub fn _run(
+    pub fn run(
         &mut self,
         sender: AccountHash,
         session_code: DeploySource,
         session_args: RuntimeArgs,
+        success: Option<bool>,
+        block_time: Option<u64>,
     ) {
+        let deploy_hash = self.rng.gen();
+        let block_time = block_time.unwrap_or(self.block_time);
         deploy(
             &mut self.builder,
             &sender,
             &session_code,
             session_args,
-            true,
-            None,
-        )
+            success,
+            Some(block_time),
+            deploy_hash,
+        );
+
+        let name = session_code.name();
+        let cost = self.builder.last_exec_gas_cost().value();
+        self.last_gas_cost = cost;
+        let max_cost = self.gas_report.entry(name.clone()).or_insert(cost);
+        if cost > *max_cost {
+            *max_cost = cost;
+        }
+        if !self.builder.is_error() {
+            if let Some(gas_budget) = &self.gas_budget {
+                gas_budget.check(&name, cost);
+            }
+        }
     }
 
     pub fn query<T: CLTyped + FromBytes>(
@@ -211,4 +526,25 @@ impl TestEnvState {
     ) -> T {
         query(&self.builder, Key::Account(account), path)
     }
+    pub fn query_contract_named_key<T: CLTyped + FromBytes>(
+        &self,
+        contract_hash: [u8; 32],
+        path: &[String],
+    ) -> T {
+        query(&self.builder, Key::Hash(contract_hash), path)
+    }
+
+    pub fn current_contract_hash(&self, package_hash: [u8; 32]) -> [u8; 32] {
+        match self
+            .builder
+            .query(None, Key::Hash(package_hash), &[])
+            .expect("should be stored value.")
+        {
+            StoredValue::ContractPackage(package) => package
+                .current_contract_hash()
+                .expect("Package has no enabled contract version.")
+                .value(),
+            _ => panic!("Provided key is not a contract package."),
+        }
+    }
 }
@@ -4,12 +4,14 @@ use casper_engine_test_support::{
     DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
     DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
 };
-use casper_execution_engine::core::engine_state::ExecuteRequest;
+use casper_execution_engine::core::{
+    engine_state::{Error as EngineStateError, ExecuteRequest},
+    execution::Error as ExecError,
+};
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, runtime_args, system::mint, CLTyped, ContractHash,
-    ContractPackageHash, Key, RuntimeArgs, StoredValue, U512,
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, system::mint, ApiError, CLTyped,
+    ContractHash, ContractPackageHash, Key, RuntimeArgs, StoredValue, U512,
 };
-use rand::Rng;
 
 pub fn query<T: FromBytes + CLTyped>(
     builder: &InMemoryWasmTestBuilder,
@@ -26,18 +28,19 @@ pub fn query<T: FromBytes + CLTyped>(
         .expect("Wrong type in query result.")
 }
 
-pub fn fund_account(account: &AccountHash) -> ExecuteRequest {
-    let mut rng = rand::thread_rng();
+pub const DEFAULT_ACCOUNT_BALANCE: u64 = 99_999_999_000_000_00u64;
+
+pub fn fund_account(account: &AccountHash, amount: U512, deploy_hash: [u8; 32]) -> ExecuteRequest {
     let deploy_item = DeployItemBuilder::new()
         .with_address(*DEFAULT_ACCOUNT_ADDR)
         .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
         .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
         .with_transfer_args(runtime_args! {
-            mint::ARG_AMOUNT => U512::from(99_999_999_000_000_00u64),
+            mint::ARG_AMOUNT => amount,
             mint::ARG_TARGET => *account,
             mint::ARG_ID => <Option::<u64>>::None
         })
-        .with_deploy_hash(rng.gen())
+        .with_deploy_hash(deploy_hash)
         .build();
 
     ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
@@ -45,6 +48,10 @@ pub fn fund_account(account: &AccountHash) -> ExecuteRequest {
 
 pub enum DeploySource {
     Code(PathBuf),
+    Bytes {
+        module_bytes: Vec<u8>,
+        name: String,
+    },
     ByContractHash {
         hash: ContractHash,
         method: String,
@@ -55,23 +62,39 @@ pub enum DeploySource {
     },
 }
 
+impl DeploySource {
+    pub fn name(&self) -> String {
+        match self {
+            DeploySource::Code(path) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            DeploySource::Bytes { name, .. } => name.clone(),
+            DeploySource::ByContractHash { method, .. }
+            | DeploySource::ByPackageHash { method, .. } => method.clone(),
+        }
+    }
+}
+
 pub fn deploy(
     builder: &mut InMemoryWasmTestBuilder,
     deployer: &AccountHash,
     source: &DeploySource,
     args: RuntimeArgs,
-    success: bool,
+    success: Option<bool>,
     block_time: Option<u64>,
+    deploy_hash: [u8; 32],
 ) {
-    let mut rng = rand::thread_rng();
-    // let deploy_hash = rng.gen();
     let mut deploy_builder = DeployItemBuilder::new()
         .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
         .with_address(*deployer)
         .with_authorization_keys(&[*deployer])
-        .with_deploy_hash(rng.gen());
+        .with_deploy_hash(deploy_hash);
     deploy_builder = match source {
         DeploySource::Code(path) => deploy_builder.with_session_code(path, args),
+        DeploySource::Bytes { module_bytes, .. } => {
+            deploy_builder.with_session_bytes(module_bytes.clone(), args)
+        }
         DeploySource::ByContractHash { hash, method } => {
             deploy_builder.with_stored_session_hash(*hash, method, args)
         }
@@ -92,14 +115,21 @@ pub fn deploy(
         execute_request_builder = execute_request_builder.with_block_time(ustamp)
     }
     let exec = builder.exec(execute_request_builder.build());
-    if success {
-        exec.expect_success()
-    } else {
-        exec.expect_failure()
+    match success {
+        Some(true) => exec.expect_success(),
+        Some(false) => exec.expect_failure(),
+        None => exec,
     }
     .commit();
 }
 
+pub fn revert_error(builder: &InMemoryWasmTestBuilder) -> Option<ApiError> {
+    match builder.get_error()? {
+        EngineStateError::Exec(ExecError::Revert(error)) => Some(error),
+        _ => None,
+    }
+}
+
 pub fn query_dictionary_item(
     builder: &InMemoryWasmTestBuilder,
     key: Key,
@@ -7,7 +7,7 @@ use casper_erc20_crate::{Address, Error, ERC20 as CasperErc20};
 use casper_types::ContractHash;
 use casper_types::Key;
 use casper_types::{ContractPackageHash, U256};
-use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use casperlabs_contract_utils::{null_key, ContractContext, ContractStorage};
 
 pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     fn init(&self, contract_hash: ContractHash, package_hash: ContractPackageHash) {
@@ -87,14 +87,13 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
         ret
     }
 
+    // Mint and burn events report the zero side as `null_key()`
+    // (`account-hash-000…`); earlier releases emitted `hash-000…` here.
     fn mint(&self, to: Address, value: U256) -> Result<(), Error> {
         let ret = CasperErc20::default().mint(to, value);
         if ret.is_ok() {
             emit(&ERC20Event::Transfer {
-                from: Key::from_formatted_str(
-                    "hash-0000000000000000000000000000000000000000000000000000000000000000",
-                )
-                .unwrap(),
+                from: null_key(),
                 to: Key::from(to),
                 value,
             });
@@ -107,10 +106,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
         if ret.is_ok() {
             emit(&ERC20Event::Transfer {
                 from: Key::from(from),
-                to: Key::from_formatted_str(
-                    "hash-0000000000000000000000000000000000000000000000000000000000000000",
-                )
-                .unwrap(),
+                to: null_key(),
                 value,
             });
         }